@@ -0,0 +1,283 @@
+use crate::{bounds, calculate_positions, Vector2D};
+use rgb::RGBA8;
+use std::{borrow::Cow, fs::File, path::Path};
+
+/// How long each frame of an animation is shown for, in hundredths of a second.
+const FRAME_DELAY: u16 = 10;
+/// How long the last frame of an animation is shown for, in hundredths of a second.
+const LAST_FRAME_DELAY: u16 = 300;
+
+/// How the cells of the plane map onto the pixels of an image. Large areas are
+/// drawn with several cells to each pixel, and small areas with each cell drawn
+/// as a square of pixels.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Scale {
+    cells_per_pixel: u64,
+    pixels_per_cell: usize,
+}
+
+impl Scale {
+    const ACTUAL_SIZE: Scale = Scale {
+        cells_per_pixel: 1,
+        pixels_per_cell: 1,
+    };
+
+    /// Finds the largest scale at which an area of cells fits inside an image of
+    /// at most the given size.
+    fn fit(width: u64, height: u64, max_width: usize, max_height: usize) -> Scale {
+        let (max_width, max_height) = (max_width.max(1) as u64, max_height.max(1) as u64);
+
+        if width <= max_width && height <= max_height {
+            Scale {
+                cells_per_pixel: 1,
+                pixels_per_cell: (max_width / width).min(max_height / height) as usize,
+            }
+        } else {
+            Scale {
+                cells_per_pixel: width.div_ceil(max_width).max(height.div_ceil(max_height)),
+                pixels_per_cell: 1,
+            }
+        }
+    }
+
+    fn pixels(self, cells: u64) -> usize {
+        cells.div_ceil(self.cells_per_pixel) as usize * self.pixels_per_cell
+    }
+}
+
+/// An image cropped to the bounds of the points, with a pixel set wherever
+/// there is a point.
+struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    /// The number of cells across and down the bounds of the points.
+    fn extent(points: &[Vector2D]) -> (u64, u64) {
+        let (min_x, min_y, max_x, max_y) = bounds(points);
+        ((max_x - min_x) as u64 + 1, (max_y - min_y) as u64 + 1)
+    }
+
+    fn draw(points: &[Vector2D], scale: Scale) -> Bitmap {
+        let (min_x, min_y, _, _) = bounds(points);
+        let (cells_across, cells_down) = Bitmap::extent(points);
+        let width = scale.pixels(cells_across);
+        let height = scale.pixels(cells_down);
+        let mut pixels = vec![false; width * height];
+
+        for point in points {
+            let column = ((point.x - min_x) as u64 / scale.cells_per_pixel) as usize;
+            let row = ((point.y - min_y) as u64 / scale.cells_per_pixel) as usize;
+
+            for y in row * scale.pixels_per_cell..(row + 1) * scale.pixels_per_cell {
+                let start = y * width + column * scale.pixels_per_cell;
+                for pixel in &mut pixels[start..start + scale.pixels_per_cell] {
+                    *pixel = true;
+                }
+            }
+        }
+
+        Bitmap {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn save<P: AsRef<Path>>(&self, filename: P) -> Result<(), lodepng::Error> {
+        let image: Vec<RGBA8> = self
+            .pixels
+            .iter()
+            .map(|&set| {
+                if set {
+                    RGBA8::new(0, 0, 0, 255)
+                } else {
+                    RGBA8::new(255, 255, 255, 255)
+                }
+            })
+            .collect();
+
+        lodepng::encode32_file(filename, &image, self.width, self.height)
+    }
+}
+
+/// Saves the points as a PNG, cropped to their bounds with one pixel for each cell.
+pub fn save_image<P: AsRef<Path>>(points: &[Vector2D], filename: P) -> Result<(), lodepng::Error> {
+    Bitmap::draw(points, Scale::ACTUAL_SIZE).save(filename)
+}
+
+/// Saves the points as a PNG no more than `max_size` pixels wide or high. The
+/// image is cropped to the bounds of the points, and then either enlarged so that
+/// each cell is a square of pixels or shrunk so that each pixel covers a square
+/// of cells, whichever fills as much of the allowed size as possible.
+pub fn save_scaled_image<P: AsRef<Path>>(
+    points: &[Vector2D],
+    max_size: usize,
+    filename: P,
+) -> Result<(), lodepng::Error> {
+    let (cells_across, cells_down) = Bitmap::extent(points);
+    let scale = Scale::fit(cells_across, cells_down, max_size, max_size);
+
+    Bitmap::draw(points, scale).save(filename)
+}
+
+/// Saves an animated GIF of the points moving, with one frame for each of the
+/// given times. The last frame is held for a few seconds before the animation
+/// starts again.
+///
+/// The animation is sized to fit the tightest frame into `max_size` pixels
+/// (at most 65,535). Every frame is cropped to the bounds of its own points and
+/// scaled to fit the animation, so frames where the points are far apart are
+/// shrunk rather than growing to a huge size.
+pub fn save_animation<P: AsRef<Path>>(
+    vectors: &[(Vector2D, Vector2D)],
+    times: impl IntoIterator<Item = i64>,
+    max_size: usize,
+    filename: P,
+) -> Result<(), gif::EncodingError> {
+    let frames: Vec<Vec<Vector2D>> = times
+        .into_iter()
+        .map(|time| calculate_positions(vectors, time))
+        .collect();
+    let max_size = max_size.min(usize::from(u16::MAX));
+
+    let (width, height) = frames
+        .iter()
+        .map(|points| Bitmap::extent(points))
+        .min_by_key(|&(across, down)| u128::from(across) * u128::from(down))
+        .map(|(across, down)| {
+            let scale = Scale::fit(across, down, max_size, max_size);
+            (scale.pixels(across), scale.pixels(down))
+        })
+        .unwrap_or((1, 1));
+
+    let palette = [255, 255, 255, 0, 0, 0];
+    let mut encoder = gif::Encoder::new(
+        File::create(filename)?,
+        width as u16,
+        height as u16,
+        &palette,
+    )?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    for (number, points) in frames.iter().enumerate() {
+        let (across, down) = Bitmap::extent(points);
+        let bitmap = Bitmap::draw(points, Scale::fit(across, down, width, height));
+
+        // Centre the frame in the animation.
+        let left = (width - bitmap.width) / 2;
+        let top = (height - bitmap.height) / 2;
+        let mut buffer = vec![0; width * height];
+        for (row, pixels) in bitmap.pixels.chunks(bitmap.width).enumerate() {
+            let start = (top + row) * width + left;
+            for (index, &set) in buffer[start..start + bitmap.width].iter_mut().zip(pixels) {
+                *index = u8::from(set);
+            }
+        }
+
+        let delay = if number + 1 == frames.len() {
+            LAST_FRAME_DELAY
+        } else {
+            FRAME_DELAY
+        };
+
+        encoder.write_frame(&gif::Frame {
+            width: width as u16,
+            height: height as u16,
+            delay,
+            buffer: Cow::Owned(buffer),
+            ..gif::Frame::default()
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_areas_are_enlarged() {
+        assert_eq!(
+            Scale {
+                cells_per_pixel: 1,
+                pixels_per_cell: 6
+            },
+            Scale::fit(62, 10, 400, 400)
+        );
+    }
+
+    #[test]
+    fn large_areas_are_shrunk() {
+        let scale = Scale::fit(100_000, 30_000, 400, 400);
+        assert_eq!(
+            Scale {
+                cells_per_pixel: 250,
+                pixels_per_cell: 1
+            },
+            scale
+        );
+        assert_eq!(400, scale.pixels(100_000));
+        assert_eq!(120, scale.pixels(30_000));
+
+        assert!(Scale::fit(100_001, 10, 400, 400).pixels(100_001) <= 400);
+    }
+
+    #[test]
+    fn draw_enlarged_points() {
+        let points = vec![Vector2D { x: -3, y: 7 }, Vector2D { x: -2, y: 8 }];
+        let bitmap = Bitmap::draw(
+            &points,
+            Scale {
+                cells_per_pixel: 1,
+                pixels_per_cell: 2,
+            },
+        );
+
+        assert_eq!((4, 4), (bitmap.width, bitmap.height));
+        let expected = [
+            true, true, false, false, //
+            true, true, false, false, //
+            false, false, true, true, //
+            false, false, true, true,
+        ];
+        assert_eq!(&expected[..], &bitmap.pixels[..]);
+    }
+
+    #[test]
+    fn draw_shrunk_points() {
+        let points = vec![
+            Vector2D { x: 0, y: 0 },
+            Vector2D { x: 1, y: 1 },
+            Vector2D { x: 5, y: 2 },
+        ];
+        let bitmap = Bitmap::draw(
+            &points,
+            Scale {
+                cells_per_pixel: 3,
+                pixels_per_cell: 1,
+            },
+        );
+
+        assert_eq!((2, 1), (bitmap.width, bitmap.height));
+        assert_eq!(vec![true, true], bitmap.pixels);
+    }
+
+    #[test]
+    fn save_animation_of_converging_points() {
+        let vectors = vec![
+            (Vector2D { x: -50_000, y: 0 }, Vector2D { x: 5, y: 0 }),
+            (Vector2D { x: 50_000, y: 3 }, Vector2D { x: -5, y: 0 }),
+        ];
+        let filename = std::env::temp_dir().join("day10_save_animation_test.gif");
+
+        save_animation(&vectors, (9_990..=10_000).step_by(2), 100, &filename).unwrap();
+
+        let contents = std::fs::read(&filename).unwrap();
+        std::fs::remove_file(&filename).unwrap();
+        assert_eq!(b"GIF89a", &contents[..6]);
+    }
+}
@@ -0,0 +1,50 @@
+mod drawing;
+mod ocr;
+mod parsing;
+mod solver;
+mod text;
+mod vectors;
+pub use crate::drawing::{save_animation, save_image, save_scaled_image};
+pub use crate::ocr::{read_message, OcrError};
+pub use crate::parsing::{parse_input, ParseError};
+pub use crate::solver::find_message_time;
+pub use crate::text::{render_text, TextStyle};
+pub use crate::vectors::{Abs, CheckedArithmetic, Vector2D};
+
+pub fn calculate_positions(
+    initial_position_velocity: &[(Vector2D, Vector2D)],
+    time: i64,
+) -> Vec<Vector2D> {
+    initial_position_velocity
+        .iter()
+        .cloned()
+        .map(|(position, velocity)| position + velocity * time)
+        .collect()
+}
+
+pub fn bounds(points: &[Vector2D]) -> (i64, i64, i64, i64) {
+    let mut min_x = points[0].x;
+    let mut min_y = points[0].y;
+    let mut max_x = points[0].x;
+    let mut max_y = points[0].y;
+
+    for point in points {
+        if point.x < min_x {
+            min_x = point.x;
+        }
+
+        if point.y < min_y {
+            min_y = point.y;
+        }
+
+        if point.x > max_x {
+            max_x = point.x;
+        }
+
+        if point.y > max_y {
+            max_y = point.y;
+        }
+    }
+
+    (min_x, min_y, max_x, max_y)
+}
@@ -0,0 +1,326 @@
+use crate::{bounds, Vector2D};
+use std::{collections::HashSet, error, fmt};
+
+/// The letters used by puzzles whose messages are six rows high.
+const SHORT_ALPHABET: &[(char, &[&str])] = &[
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// The letters used by puzzles whose messages are ten rows high, such as this one.
+const TALL_ALPHABET: &[(char, &[&str])] = &[
+    (
+        'A',
+        &[
+            "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'B',
+        &[
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+            "#....#", "#####.",
+        ],
+    ),
+    (
+        'C',
+        &[
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#....#", ".####.",
+        ],
+    ),
+    (
+        'E',
+        &[
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'F',
+        &[
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'G',
+        &[
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+            "#...##", ".###.#",
+        ],
+    ),
+    (
+        'H',
+        &[
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'J',
+        &[
+            "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+            "#...#.", ".###..",
+        ],
+    ),
+    (
+        'K',
+        &[
+            "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+            "#...#.", "#....#",
+        ],
+    ),
+    (
+        'L',
+        &[
+            "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'N',
+        &[
+            "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+            "#...##", "#....#",
+        ],
+    ),
+    (
+        'P',
+        &[
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'R',
+        &[
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'X',
+        &[
+            "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'Z',
+        &[
+            "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OcrError {
+    NoPoints,
+    UnsupportedHeight { height: i64 },
+    UnknownLetter { position: usize },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::NoPoints => write!(f, "There are no points to read"),
+            OcrError::UnsupportedHeight { height } => write!(
+                f,
+                "The message is {} rows high, but only letters 6 or 10 rows high can be read",
+                height
+            ),
+            OcrError::UnknownLetter { position } => {
+                write!(
+                    f,
+                    "Letter {} of the message is not recognised",
+                    position + 1
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for OcrError {}
+
+/// Reads the message spelt out by the points.
+///
+/// The letters are found by splitting the message wherever there is a column
+/// with no points in it. Each letter is then compared with the known letters of
+/// the same height, ignoring any empty columns at the sides of the letters.
+pub fn read_message(points: &[Vector2D]) -> Result<String, OcrError> {
+    if points.is_empty() {
+        return Err(OcrError::NoPoints);
+    }
+
+    let (min_x, min_y, max_x, max_y) = bounds(points);
+    let height = max_y - min_y + 1;
+    let alphabet = match height {
+        6 => SHORT_ALPHABET,
+        10 => TALL_ALPHABET,
+        _ => return Err(OcrError::UnsupportedHeight { height }),
+    };
+
+    let point_set: HashSet<Vector2D> = points.iter().cloned().collect();
+    let row = |y: i64, columns: &[i64]| -> String {
+        columns
+            .iter()
+            .map(|&x| {
+                if point_set.contains(&Vector2D { x, y }) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect()
+    };
+
+    let mut letters: Vec<Vec<i64>> = Vec::new();
+    let mut in_letter = false;
+    for x in min_x..=max_x {
+        if (min_y..=max_y).any(|y| point_set.contains(&Vector2D { x, y })) {
+            if !in_letter {
+                letters.push(Vec::new());
+                in_letter = true;
+            }
+            letters.last_mut().unwrap().push(x);
+        } else {
+            in_letter = false;
+        }
+    }
+
+    letters
+        .iter()
+        .enumerate()
+        .map(|(position, columns)| {
+            let glyph: Vec<String> = (min_y..=max_y).map(|y| row(y, columns)).collect();
+
+            alphabet
+                .iter()
+                .find(|(_, rows)| trim_columns(rows) == glyph)
+                .map(|&(letter, _)| letter)
+                .ok_or(OcrError::UnknownLetter { position })
+        })
+        .collect()
+}
+
+/// Removes any columns at either side of a letter which have no points in them.
+fn trim_columns(rows: &[&str]) -> Vec<String> {
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let filled = |column: usize| {
+        rows.iter()
+            .any(|row| row.as_bytes().get(column) == Some(&b'#'))
+    };
+
+    let first = (0..width).find(|&column| filled(column)).unwrap_or(0);
+    let last = (0..width).rev().find(|&column| filled(column)).unwrap_or(0);
+
+    rows.iter()
+        .map(|row| row.get(first..=last).unwrap_or("").to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points_from_text(text: &str) -> Vec<Vector2D> {
+        text.lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(x, _)| Vector2D {
+                        x: x as i64 + 100,
+                        y: y as i64 - 50,
+                    })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn read_tall_message() {
+        let message = "\
+#....#..######..#.......#####...#....#..#....#...####...#....#
+#...#...#.......#.......#....#..#....#..#....#..#....#..#...#.
+#..#....#.......#.......#....#..#....#...#..#...#.......#..#..
+#.#.....#.......#.......#....#..#....#...#..#...#.......#.#...
+##......#####...#.......#####...######....##....#.......##....
+##......#.......#.......#....#..#....#....##....#..###..##....
+#.#.....#.......#.......#....#..#....#...#..#...#....#..#.#...
+#..#....#.......#.......#....#..#....#...#..#...#....#..#..#..
+#...#...#.......#.......#....#..#....#..#....#..#...##..#...#.
+#....#..#.......######..#####...#....#..#....#...###.#..#....#";
+
+        assert_eq!(
+            Ok("KFLBHXGK".to_string()),
+            read_message(&points_from_text(message))
+        );
+    }
+
+    #[test]
+    fn read_short_message() {
+        let message = "\
+#..#.###..#...#.####
+#..#..#...#...#....#
+####..#....#.#....#.
+#..#..#.....#....#..
+#..#..#.....#...#...
+#..#.###....#...####";
+
+        assert_eq!(
+            Ok("HIYZ".to_string()),
+            read_message(&points_from_text(message))
+        );
+    }
+
+    #[test]
+    fn unreadable_messages() {
+        assert_eq!(Err(OcrError::NoPoints), read_message(&[]));
+
+        let example_hi = "\
+#...#..###
+#...#...#.
+#...#...#.
+#####...#.
+#...#...#.
+#...#...#.
+#...#...#.
+#...#..###";
+        assert_eq!(
+            Err(OcrError::UnsupportedHeight { height: 8 }),
+            read_message(&points_from_text(example_hi))
+        );
+
+        let unknown_second_letter = "\
+####.#..#
+#....##.#
+###..#.##
+#....#..#
+#....#..#
+####.#..#";
+        assert_eq!(
+            Err(OcrError::UnknownLetter { position: 1 }),
+            read_message(&points_from_text(unknown_second_letter))
+        );
+    }
+}
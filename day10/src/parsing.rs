@@ -4,12 +4,11 @@ use regex::Regex;
 use std::{error, fmt, num::ParseIntError};
 
 lazy_static! {
-    static ref INPUT_REGEX: Regex =
-        Regex::new(r"position=<\s*(\-?\d+),\s*(\-?\d+)>\s+velocity=<\s*(\-?\d+),\s*(\-?\d+)>")
-            .expect("Cannot parse regular expression");
+    static ref INPUT_REGEX: Regex = Regex::new(r"position=(<[^>]*>)\s+velocity=(<[^>]*>)")
+        .expect("Cannot parse regular expression");
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum ParseError {
     IncorrectFormat,
     InvalidNumber,
@@ -34,16 +33,9 @@ impl error::Error for ParseError {}
 
 pub fn parse_input(input: &str) -> Result<(Vector2D, Vector2D), ParseError> {
     match INPUT_REGEX.captures(input) {
-        Some(ref captures) if captures.len() == 5 => {
-            let position = Vector2D {
-                x: captures[1].parse()?,
-                y: captures[2].parse()?,
-            };
-
-            let velocity = Vector2D {
-                x: captures[3].parse()?,
-                y: captures[4].parse()?,
-            };
+        Some(ref captures) if captures.len() == 3 => {
+            let position = captures[1].parse()?;
+            let velocity = captures[2].parse()?;
 
             Ok((position, velocity))
         }
@@ -0,0 +1,149 @@
+use crate::{bounds, calculate_positions, Vector2D};
+
+/// Finds the time at which the points are closest together, which is when the
+/// message appears.
+///
+/// The sum of the squared distances of the points from their centre of mass is a
+/// quadratic in t, so its minimum can be found directly from the positions and
+/// velocities. Starting from that estimate, the time moves one second at a time
+/// in whichever direction makes the bounding box of the points smaller, until
+/// neither neighbouring second is any better. The answer is never before t = 0.
+pub fn find_message_time(vectors: &[(Vector2D, Vector2D)]) -> i64 {
+    if vectors.is_empty() {
+        return 0;
+    }
+
+    let estimate = match spread_minimum(vectors) {
+        Some((numerator, denominator)) => floor_div(numerator, denominator).max(0),
+        None => 0,
+    };
+
+    smallest_bounding_box_near(vectors, estimate)
+}
+
+/// Finds the time at which the spread of the points is smallest, as a fraction,
+/// or `None` if all of the points move with the same velocity (so the spread
+/// never changes).
+///
+/// Relative to the centre of mass, a point is at `dp + dv * t`, so the spread is
+/// `A t^2 + 2 B t + C`, with `A` the sum of `dv . dv` and `B` the sum of `dp . dv`.
+/// This has its minimum at `t = -B / A`. Multiplying every relative position and
+/// velocity by the number of points keeps the arithmetic in whole numbers.
+fn spread_minimum(vectors: &[(Vector2D, Vector2D)]) -> Option<(i128, i128)> {
+    let count = vectors.len() as i128;
+    let (mut total_px, mut total_py, mut total_vx, mut total_vy) = (0i128, 0i128, 0i128, 0i128);
+
+    for (position, velocity) in vectors {
+        total_px += i128::from(position.x);
+        total_py += i128::from(position.y);
+        total_vx += i128::from(velocity.x);
+        total_vy += i128::from(velocity.y);
+    }
+
+    let (mut a, mut b) = (0i128, 0i128);
+
+    for (position, velocity) in vectors {
+        let dpx = count * i128::from(position.x) - total_px;
+        let dpy = count * i128::from(position.y) - total_py;
+        let dvx = count * i128::from(velocity.x) - total_vx;
+        let dvy = count * i128::from(velocity.y) - total_vy;
+
+        a += dvx * dvx + dvy * dvy;
+        b += dpx * dvx + dpy * dvy;
+    }
+
+    if a == 0 {
+        None
+    } else {
+        Some((-b, a))
+    }
+}
+
+/// Rounds a fraction with a positive denominator down to a whole number.
+fn floor_div(numerator: i128, denominator: i128) -> i64 {
+    let quotient = numerator.div_euclid(denominator);
+    quotient.clamp(i128::from(i64::MIN), i128::from(i64::MAX)) as i64
+}
+
+fn bounding_box_area(vectors: &[(Vector2D, Vector2D)], time: i64) -> i128 {
+    let (min_x, min_y, max_x, max_y) = bounds(&calculate_positions(vectors, time));
+
+    (i128::from(max_x) - i128::from(min_x) + 1) * (i128::from(max_y) - i128::from(min_y) + 1)
+}
+
+/// Searches outwards from a starting time for the nearest time (not before 0)
+/// at which the bounding box of the points is smaller than at the times either
+/// side of it.
+fn smallest_bounding_box_near(vectors: &[(Vector2D, Vector2D)], start: i64) -> i64 {
+    let mut time = start;
+    let mut area = bounding_box_area(vectors, time);
+
+    loop {
+        let earlier = if time > 0 {
+            Some(bounding_box_area(vectors, time - 1))
+        } else {
+            None
+        };
+        let later = bounding_box_area(vectors, time + 1);
+
+        match earlier {
+            Some(earlier) if earlier < area && earlier <= later => {
+                time -= 1;
+                area = earlier;
+            }
+            _ if later < area => {
+                time += 1;
+                area = later;
+            }
+            _ => return time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    fn example() -> Vec<(Vector2D, Vector2D)> {
+        include_str!("../example_input.txt")
+            .lines()
+            .map(|line| parse_input(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn example_message_time() {
+        assert_eq!(3, find_message_time(&example()));
+    }
+
+    #[test]
+    fn far_off_starting_positions() {
+        let start = 1_000_000_000;
+        let vectors: Vec<(Vector2D, Vector2D)> = example()
+            .into_iter()
+            .map(|(position, velocity)| (position - velocity * start, velocity))
+            .collect();
+
+        assert_eq!(start + 3, find_message_time(&vectors));
+    }
+
+    #[test]
+    fn points_moving_together() {
+        let velocity = Vector2D { x: 3, y: -1 };
+        let vectors = vec![
+            (Vector2D { x: 0, y: 0 }, velocity),
+            (Vector2D { x: 5, y: 2 }, velocity),
+        ];
+
+        assert_eq!(None, spread_minimum(&vectors));
+        assert_eq!(0, find_message_time(&vectors));
+    }
+
+    #[test]
+    fn floor_div_rounds_down() {
+        assert_eq!(2, floor_div(7, 3));
+        assert_eq!(-3, floor_div(-7, 3));
+        assert_eq!(-2, floor_div(-6, 3));
+    }
+}
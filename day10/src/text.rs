@@ -0,0 +1,132 @@
+use crate::{bounds, Vector2D};
+use std::collections::HashSet;
+
+/// The characters used to draw the points as text.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TextStyle {
+    /// One character for each cell: `#` for a point and `.` for empty space.
+    Ascii,
+    /// One character for each pair of cells above one another, using the
+    /// Unicode half block characters.
+    HalfBlock,
+    /// One character for each block of cells two wide and four high, using the
+    /// Unicode braille patterns.
+    Braille,
+}
+
+impl TextStyle {
+    /// The number of cells across and down drawn by each character.
+    fn cell_size(self) -> (i64, i64) {
+        match self {
+            TextStyle::Ascii => (1, 1),
+            TextStyle::HalfBlock => (1, 2),
+            TextStyle::Braille => (2, 4),
+        }
+    }
+
+    /// Chooses the character for a block of cells, given which of them hold a
+    /// point. `filled(column, row)` is relative to the top left of the block.
+    fn character(self, filled: impl Fn(i64, i64) -> bool) -> char {
+        match self {
+            TextStyle::Ascii => {
+                if filled(0, 0) {
+                    '#'
+                } else {
+                    '.'
+                }
+            }
+            TextStyle::HalfBlock => match (filled(0, 0), filled(0, 1)) {
+                (true, true) => '\u{2588}',
+                (true, false) => '\u{2580}',
+                (false, true) => '\u{2584}',
+                (false, false) => ' ',
+            },
+            TextStyle::Braille => {
+                // The bit for each dot of a braille pattern, by row and then column.
+                const DOTS: [[u32; 2]; 4] =
+                    [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+                let mut pattern = 0;
+                for (row, bits) in DOTS.iter().enumerate() {
+                    for (column, bit) in bits.iter().enumerate() {
+                        if filled(column as i64, row as i64) {
+                            pattern |= bit;
+                        }
+                    }
+                }
+
+                std::char::from_u32(0x2800 + pattern).unwrap()
+            }
+        }
+    }
+}
+
+/// Draws the points as lines of text, cropped to the bounds of the points, so
+/// that the message can be read in a terminal.
+pub fn render_text(points: &[Vector2D], style: TextStyle) -> String {
+    if points.is_empty() {
+        return String::new();
+    }
+
+    let (min_x, min_y, max_x, max_y) = bounds(points);
+    let point_set: HashSet<Vector2D> = points.iter().cloned().collect();
+    let (across, down) = style.cell_size();
+    let mut text = String::new();
+
+    for top in (min_y..=max_y).step_by(down as usize) {
+        for left in (min_x..=max_x).step_by(across as usize) {
+            text.push(style.character(|column, row| {
+                point_set.contains(&Vector2D {
+                    x: left + column,
+                    y: top + row,
+                })
+            }));
+        }
+        text.push('\n');
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points() -> Vec<Vector2D> {
+        // #..
+        // .#.
+        // ..#
+        vec![
+            Vector2D { x: 10, y: -1 },
+            Vector2D { x: 11, y: 0 },
+            Vector2D { x: 12, y: 1 },
+        ]
+    }
+
+    #[test]
+    fn ascii_text() {
+        assert_eq!("#..\n.#.\n..#\n", render_text(&points(), TextStyle::Ascii));
+    }
+
+    #[test]
+    fn half_block_text() {
+        assert_eq!(
+            "\u{2580}\u{2584} \n  \u{2580}\n",
+            render_text(&points(), TextStyle::HalfBlock)
+        );
+    }
+
+    #[test]
+    fn braille_text() {
+        // Dots 1 and 5 in the first character, and dot 3 in the second.
+        assert_eq!(
+            "\u{2811}\u{2804}\n",
+            render_text(&points(), TextStyle::Braille)
+        );
+    }
+
+    #[test]
+    fn no_points() {
+        assert_eq!("", render_text(&[], TextStyle::Ascii));
+    }
+}
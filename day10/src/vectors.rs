@@ -1,24 +1,183 @@
+use crate::parsing::ParseError;
 use std::{
+    fmt,
     iter::Sum,
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    str::FromStr,
 };
 
+/// A two-dimensional vector. The components can be any integer or floating
+/// point type, and are `i64` unless another type is given.
 #[derive(Debug, Copy, Clone, Default, Hash, Eq, PartialEq)]
-pub struct Vector2D {
-    pub x: i64,
-    pub y: i64,
+pub struct Vector2D<T = i64> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Vector2D {
-    pub fn abs_square(self) -> i64 {
-        self.x * self.x + self.y * self.y
+impl<T> Vector2D<T> {
+    pub fn new(x: T, y: T) -> Vector2D<T> {
+        Vector2D { x, y }
     }
 }
 
-impl Add for Vector2D {
-    type Output = Vector2D;
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Vector2D<T> {
+    pub fn dot(self, other: Vector2D<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn abs_square(self) -> T {
+        self.dot(self)
+    }
+}
+
+impl<T: Abs + Add<Output = T>> Vector2D<T> {
+    /// The sum of the absolute values of the components.
+    pub fn manhattan_length(self) -> T {
+        self.x.abs() + self.y.abs()
+    }
+}
+
+impl<T: PartialOrd> Vector2D<T> {
+    /// The smaller of each pair of components.
+    pub fn min(self, other: Vector2D<T>) -> Vector2D<T> {
+        Vector2D {
+            x: if other.x < self.x { other.x } else { self.x },
+            y: if other.y < self.y { other.y } else { self.y },
+        }
+    }
+
+    /// The larger of each pair of components.
+    pub fn max(self, other: Vector2D<T>) -> Vector2D<T> {
+        Vector2D {
+            x: if other.x > self.x { other.x } else { self.x },
+            y: if other.y > self.y { other.y } else { self.y },
+        }
+    }
+}
+
+/// Arithmetic which returns `None` instead of overflowing or dividing by zero.
+impl<T: CheckedArithmetic> Vector2D<T> {
+    pub fn checked_add(self, other: Vector2D<T>) -> Option<Vector2D<T>> {
+        Some(Vector2D {
+            x: self.x.checked_add(other.x)?,
+            y: self.y.checked_add(other.y)?,
+        })
+    }
+
+    pub fn checked_sub(self, other: Vector2D<T>) -> Option<Vector2D<T>> {
+        Some(Vector2D {
+            x: self.x.checked_sub(other.x)?,
+            y: self.y.checked_sub(other.y)?,
+        })
+    }
+
+    pub fn checked_mul(self, rhs: T) -> Option<Vector2D<T>> {
+        Some(Vector2D {
+            x: self.x.checked_mul(rhs)?,
+            y: self.y.checked_mul(rhs)?,
+        })
+    }
+
+    /// Divides both components exactly. Returns `None` if either component is
+    /// not a multiple of `rhs`, rather than rounding as `/` does.
+    pub fn checked_div(self, rhs: T) -> Option<Vector2D<T>> {
+        if !self.x.checked_rem(rhs)?.is_zero() || !self.y.checked_rem(rhs)?.is_zero() {
+            return None;
+        }
+
+        Some(Vector2D {
+            x: self.x.checked_div(rhs)?,
+            y: self.y.checked_div(rhs)?,
+        })
+    }
+
+    pub fn checked_neg(self) -> Option<Vector2D<T>> {
+        Some(Vector2D {
+            x: self.x.checked_neg()?,
+            y: self.y.checked_neg()?,
+        })
+    }
+}
+
+/// Numbers which have an absolute value.
+pub trait Abs {
+    fn abs(self) -> Self;
+}
+
+/// Integers with arithmetic which reports overflow and division by zero.
+pub trait CheckedArithmetic: Copy + Sized {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+    fn checked_neg(self) -> Option<Self>;
+    fn is_zero(self) -> bool;
+}
+
+macro_rules! impl_abs {
+    ($($signed:ty)*; $($unsigned:ty)*) => {
+        $(
+            impl Abs for $signed {
+                fn abs(self) -> $signed {
+                    <$signed>::abs(self)
+                }
+            }
+        )*
+        $(
+            impl Abs for $unsigned {
+                fn abs(self) -> $unsigned {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_abs!(i8 i16 i32 i64 i128 isize f32 f64; u8 u16 u32 u64 u128 usize);
+
+macro_rules! impl_checked_arithmetic {
+    ($($integer:ty)*) => {
+        $(
+            impl CheckedArithmetic for $integer {
+                fn checked_add(self, rhs: $integer) -> Option<$integer> {
+                    <$integer>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: $integer) -> Option<$integer> {
+                    <$integer>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: $integer) -> Option<$integer> {
+                    <$integer>::checked_mul(self, rhs)
+                }
 
-    fn add(self, other: Vector2D) -> Vector2D {
+                fn checked_div(self, rhs: $integer) -> Option<$integer> {
+                    <$integer>::checked_div(self, rhs)
+                }
+
+                fn checked_rem(self, rhs: $integer) -> Option<$integer> {
+                    <$integer>::checked_rem(self, rhs)
+                }
+
+                fn checked_neg(self) -> Option<$integer> {
+                    <$integer>::checked_neg(self)
+                }
+
+                fn is_zero(self) -> bool {
+                    self == 0
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_arithmetic!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+impl<T: Add<Output = T>> Add for Vector2D<T> {
+    type Output = Vector2D<T>;
+
+    fn add(self, other: Vector2D<T>) -> Vector2D<T> {
         Vector2D {
             x: self.x + other.x,
             y: self.y + other.y,
@@ -26,17 +185,17 @@ impl Add for Vector2D {
     }
 }
 
-impl AddAssign for Vector2D {
-    fn add_assign(&mut self, other: Vector2D) {
+impl<T: AddAssign> AddAssign for Vector2D<T> {
+    fn add_assign(&mut self, other: Vector2D<T>) {
         self.x += other.x;
         self.y += other.y;
     }
 }
 
-impl Sum for Vector2D {
+impl<T: AddAssign + Default> Sum for Vector2D<T> {
     fn sum<I>(iter: I) -> Self
     where
-        I: Iterator<Item = Vector2D>,
+        I: Iterator<Item = Vector2D<T>>,
     {
         let mut total = Vector2D::default();
 
@@ -48,10 +207,10 @@ impl Sum for Vector2D {
     }
 }
 
-impl Sub for Vector2D {
-    type Output = Vector2D;
+impl<T: Sub<Output = T>> Sub for Vector2D<T> {
+    type Output = Vector2D<T>;
 
-    fn sub(self, other: Vector2D) -> Vector2D {
+    fn sub(self, other: Vector2D<T>) -> Vector2D<T> {
         Vector2D {
             x: self.x - other.x,
             y: self.y - other.y,
@@ -59,17 +218,28 @@ impl Sub for Vector2D {
     }
 }
 
-impl SubAssign for Vector2D {
-    fn sub_assign(&mut self, other: Vector2D) {
+impl<T: SubAssign> SubAssign for Vector2D<T> {
+    fn sub_assign(&mut self, other: Vector2D<T>) {
         self.x -= other.x;
         self.y -= other.y;
     }
 }
 
-impl Mul<i64> for Vector2D {
-    type Output = Vector2D;
+impl<T: Neg<Output = T>> Neg for Vector2D<T> {
+    type Output = Vector2D<T>;
+
+    fn neg(self) -> Vector2D<T> {
+        Vector2D {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vector2D<T> {
+    type Output = Vector2D<T>;
 
-    fn mul(self, rhs: i64) -> Vector2D {
+    fn mul(self, rhs: T) -> Vector2D<T> {
         Vector2D {
             x: self.x * rhs,
             y: self.y * rhs,
@@ -77,17 +247,19 @@ impl Mul<i64> for Vector2D {
     }
 }
 
-impl MulAssign<i64> for Vector2D {
-    fn mul_assign(&mut self, rhs: i64) {
+impl<T: Copy + MulAssign> MulAssign<T> for Vector2D<T> {
+    fn mul_assign(&mut self, rhs: T) {
         self.x *= rhs;
         self.y *= rhs;
     }
 }
 
-impl Div<i64> for Vector2D {
-    type Output = Vector2D;
+/// Divides both components. For integers this rounds towards zero, as integer
+/// division does; `checked_div` instead reports any remainder or division by zero.
+impl<T: Copy + Div<Output = T>> Div<T> for Vector2D<T> {
+    type Output = Vector2D<T>;
 
-    fn div(self, rhs: i64) -> Vector2D {
+    fn div(self, rhs: T) -> Vector2D<T> {
         Vector2D {
             x: self.x / rhs,
             y: self.y / rhs,
@@ -95,9 +267,121 @@ impl Div<i64> for Vector2D {
     }
 }
 
-impl DivAssign<i64> for Vector2D {
-    fn div_assign(&mut self, rhs: i64) {
+impl<T: Copy + DivAssign> DivAssign<T> for Vector2D<T> {
+    fn div_assign(&mut self, rhs: T) {
         self.x /= rhs;
         self.y /= rhs;
     }
 }
+
+/// Writes the vector in the same form as the puzzle input, for example `<3, -2>`.
+impl<T: fmt::Display> fmt::Display for Vector2D<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+/// Reads a vector in the same form as the puzzle input, for example `< 3, -2>`.
+/// Spaces are allowed around the components.
+impl<T: FromStr> FromStr for Vector2D<T> {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Vector2D<T>, ParseError> {
+        let components = text
+            .trim()
+            .strip_prefix('<')
+            .and_then(|text| text.strip_suffix('>'))
+            .ok_or(ParseError::IncorrectFormat)?;
+
+        let mut components = components.split(',');
+        match (components.next(), components.next(), components.next()) {
+            (Some(x), Some(y), None) => Ok(Vector2D {
+                x: x.trim().parse().map_err(|_| ParseError::InvalidNumber)?,
+                y: y.trim().parse().map_err(|_| ParseError::InvalidNumber)?,
+            }),
+            _ => Err(ParseError::IncorrectFormat),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators() {
+        let a = Vector2D::new(3, -4);
+        let b = Vector2D::new(-1, 2);
+
+        assert_eq!(Vector2D::new(2, -2), a + b);
+        assert_eq!(Vector2D::new(4, -6), a - b);
+        assert_eq!(Vector2D::new(-3, 4), -a);
+        assert_eq!(Vector2D::new(6, -8), a * 2);
+        assert_eq!(Vector2D::new(1, -1), a / 3);
+        assert_eq!(Vector2D::new(2, -2), vec![a, b].into_iter().sum());
+    }
+
+    #[test]
+    fn products_and_lengths() {
+        let a = Vector2D::new(3, -4);
+        let b = Vector2D::new(-1, 2);
+
+        assert_eq!(-11, a.dot(b));
+        assert_eq!(25, a.abs_square());
+        assert_eq!(7, a.manhattan_length());
+        assert_eq!(Vector2D::new(-1, -4), a.min(b));
+        assert_eq!(Vector2D::new(3, 2), a.max(b));
+    }
+
+    #[test]
+    fn floating_point_vectors() {
+        let a = Vector2D::new(1.5, -2.0);
+
+        assert_eq!(Vector2D::new(0.75, -1.0), a / 2.0);
+        assert_eq!(3.5, a.manhattan_length());
+        assert_eq!(Ok(Vector2D::new(0.5, -1e3)), "<0.5, -1e3>".parse());
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let big = Vector2D::new(i64::MAX, 0);
+
+        assert_eq!(None, big.checked_add(Vector2D::new(1, 0)));
+        assert_eq!(
+            Some(Vector2D::new(i64::MAX, 1)),
+            big.checked_add(Vector2D::new(0, 1))
+        );
+        assert_eq!(
+            None,
+            Vector2D::new(0, i64::MIN).checked_sub(Vector2D::new(0, 1))
+        );
+        assert_eq!(None, big.checked_mul(2));
+        assert_eq!(None, big.checked_div(0));
+        assert_eq!(Vector2D::new(1, -1), Vector2D::new(3, -4) / 3);
+        assert_eq!(None, Vector2D::new(3, -4).checked_div(3));
+        assert_eq!(
+            Some(Vector2D::new(1, -2)),
+            Vector2D::new(3, -6).checked_div(3)
+        );
+        assert_eq!(None, Vector2D::new(i64::MIN, 0).checked_div(-1));
+        assert_eq!(None, Vector2D::new(i64::MIN, 0).checked_neg());
+        assert_eq!(None, Vector2D::<u8>::new(1, 0).checked_neg());
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(Ok(Vector2D::new(9, 1)), "< 9,  1>".parse());
+        assert_eq!(Ok(Vector2D::new(-21209, 2)), "<-21209,2>".parse());
+        assert_eq!("<3, -2>", Vector2D::new(3, -2).to_string());
+
+        assert_eq!(
+            Err(ParseError::IncorrectFormat),
+            "3, -2".parse::<Vector2D>()
+        );
+        assert_eq!(
+            Err(ParseError::IncorrectFormat),
+            "<1, 2, 3>".parse::<Vector2D>()
+        );
+        assert_eq!(Err(ParseError::InvalidNumber), "<1, x>".parse::<Vector2D>());
+    }
+}
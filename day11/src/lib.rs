@@ -0,0 +1,319 @@
+mod summed_area;
+pub use crate::summed_area::SummedAreaTable;
+use std::ops::{Index, IndexMut};
+
+fn hundreds(x: i32) -> i32 {
+    ((x % 1000) - (x % 100)) / 100
+}
+
+fn power(x: i32, y: i32, serial: i32) -> i32 {
+    let rack = x + 10;
+    hundreds(rack * (rack * y + serial)) - 5
+}
+
+/// A rectangular grid of cells, indexed by `grid[(x, y)]` with (0, 0) at the top left.
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    items: Vec<T>,
+}
+
+impl<T: Default + Copy> Grid<T> {
+    pub fn new(width: usize, height: usize) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            items: vec![T::default(); width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl Grid<i32> {
+    /// Builds a table from which the sum of any rectangle of the grid can be
+    /// found in constant time.
+    pub fn summed_area_table(&self) -> SummedAreaTable {
+        SummedAreaTable::new(self)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width, "Column {} is outside the grid", x);
+        &self.items[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width, "Column {} is outside the grid", x);
+        &mut self.items[y * self.width + x]
+    }
+}
+
+/// Works out the power level of every fuel cell in a grid `width` cells wide and
+/// `height` cells high. Fuel cells are numbered from 1, so the cell at (x, y) in
+/// the puzzle is stored at `grid[(x - 1, y - 1)]`.
+pub fn compute_power_grid(width: usize, height: usize, serial: i32) -> Grid<i32> {
+    let mut grid = Grid::new(width, height);
+
+    for y in 0..height {
+        for x in 0..width {
+            grid[(x, y)] = power(x as i32 + 1, y as i32 + 1, serial);
+        }
+    }
+
+    grid
+}
+
+/// A rectangle of fuel cells to search for squares in, given by the puzzle
+/// coordinates of its top left cell and its size.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Window {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Window {
+    /// The whole of a grid.
+    pub fn whole<T>(grid: &Grid<T>) -> Window {
+        Window {
+            x: 1,
+            y: 1,
+            width: grid.width(),
+            height: grid.height(),
+        }
+    }
+
+    /// The part of the window which lies inside a grid.
+    fn clip<T>(self, grid: &Grid<T>) -> Window {
+        let x = self.x.max(1);
+        let y = self.y.max(1);
+        let right = (self.x + self.width).min(grid.width() + 1);
+        let bottom = (self.y + self.height).min(grid.height() + 1);
+
+        Window {
+            x,
+            y,
+            width: right.saturating_sub(x),
+            height: bottom.saturating_sub(y),
+        }
+    }
+}
+
+/// Finds the square of the given size inside the window with the highest total
+/// power. Returns the puzzle coordinates of its top left cell, its size and its
+/// power, or `None` if no square of that size fits in the window. Squares which
+/// stick out of the window (or the grid) are not considered.
+pub fn find_best_square_of_size(
+    square_size: usize,
+    grid: &Grid<i32>,
+    window: Window,
+) -> Option<(usize, usize, usize, i32)> {
+    best_square_of_size(square_size, window.clip(grid), &grid.summed_area_table())
+}
+
+fn best_square_of_size(
+    square_size: usize,
+    window: Window,
+    sums: &SummedAreaTable,
+) -> Option<(usize, usize, usize, i32)> {
+    if square_size == 0 || square_size > window.width || square_size > window.height {
+        return None;
+    }
+
+    let mut best: Option<(usize, usize, usize, i32)> = None;
+
+    for x in window.x..=window.x + window.width - square_size {
+        for y in window.y..=window.y + window.height - square_size {
+            let power = sums.rect_sum(x - 1, y - 1, square_size, square_size);
+            if best.is_none_or(|(_, _, _, best_power)| power > best_power) {
+                best = Some((x, y, square_size, power));
+            }
+        }
+    }
+
+    best
+}
+
+/// Finds the square of any size inside the window with the highest total power.
+/// Returns the puzzle coordinates of its top left cell, its size and its power,
+/// or `None` if the window is empty. If squares of different sizes have the same
+/// power, the largest is chosen.
+pub fn find_best_square_any_size(
+    grid: &Grid<i32>,
+    window: Window,
+) -> Option<(usize, usize, usize, i32)> {
+    let window = window.clip(grid);
+    let sums = grid.summed_area_table();
+    let mut best: Option<(usize, usize, usize, i32)> = None;
+
+    for square_size in (1..=window.width.min(window.height)).rev() {
+        if let Some(square) = best_square_of_size(square_size, window, &sums) {
+            if best.is_none_or(|(_, _, _, best_power)| square.3 > best_power) {
+                best = Some(square);
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hundreds() {
+        assert_eq!(2, hundreds(1234));
+        assert_eq!(0, hundreds(99));
+    }
+
+    #[test]
+    fn example_power() {
+        assert_eq!(4, power(3, 5, 8));
+        assert_eq!(-5, power(122, 79, 57));
+        assert_eq!(0, power(217, 196, 39));
+        assert_eq!(4, power(101, 153, 71));
+    }
+
+    #[test]
+    fn example_grid() {
+        let grid = compute_power_grid(300, 300, 18);
+        assert_eq!(4, grid[(32, 44)]);
+        assert_eq!(-2, grid[(31, 43)]);
+    }
+
+    #[test]
+    fn every_cell_is_filled() {
+        let grid = compute_power_grid(7, 5, 18);
+
+        assert_eq!((7, 5), (grid.width(), grid.height()));
+        for y in 0..5 {
+            for x in 0..7 {
+                assert_eq!(power(x as i32 + 1, y as i32 + 1, 18), grid[(x, y)]);
+            }
+        }
+    }
+
+    #[test]
+    fn rect_sums_match_cell_sums() {
+        let grid = compute_power_grid(20, 12, 18);
+        let sums = grid.summed_area_table();
+
+        for &(x, y, w, h) in &[(1, 1, 3, 3), (5, 2, 1, 7), (0, 0, 20, 12), (4, 9, 0, 3)] {
+            let expected: i32 = (x..x + w)
+                .flat_map(|column| (y..y + h).map(move |row| (column, row)))
+                .map(|(column, row)| grid[(column, row)])
+                .sum();
+            assert_eq!(expected, sums.rect_sum(x, y, w, h));
+        }
+    }
+
+    #[test]
+    fn part1_example1() {
+        let grid = compute_power_grid(300, 300, 18);
+        assert_eq!(
+            Some((33, 45, 3, 29)),
+            find_best_square_of_size(3, &grid, Window::whole(&grid))
+        );
+    }
+
+    #[test]
+    fn part1_example2() {
+        let grid = compute_power_grid(300, 300, 42);
+        assert_eq!(
+            Some((21, 61, 3, 30)),
+            find_best_square_of_size(3, &grid, Window::whole(&grid))
+        );
+    }
+
+    #[test]
+    fn part2_example1() {
+        let grid = compute_power_grid(300, 300, 18);
+        assert_eq!(
+            Some((90, 269, 16, 113)),
+            find_best_square_any_size(&grid, Window::whole(&grid))
+        );
+    }
+
+    #[test]
+    fn part2_example2() {
+        let grid = compute_power_grid(300, 300, 42);
+        assert_eq!(
+            Some((232, 251, 12, 119)),
+            find_best_square_any_size(&grid, Window::whole(&grid))
+        );
+    }
+
+    #[test]
+    fn search_windows() {
+        let grid = compute_power_grid(300, 300, 18);
+        let around_example = Window {
+            x: 30,
+            y: 40,
+            width: 10,
+            height: 8,
+        };
+
+        assert_eq!(
+            Some((33, 45, 3, 29)),
+            find_best_square_of_size(3, &grid, around_example)
+        );
+        // The example square sticks out of the bottom of a window one row shorter.
+        let shorter = Window {
+            height: 7,
+            ..around_example
+        };
+        assert_ne!(
+            Some((33, 45, 3, 29)),
+            find_best_square_of_size(3, &grid, shorter)
+        );
+        assert_eq!(None, find_best_square_of_size(8, &grid, shorter));
+    }
+
+    #[test]
+    fn squares_can_reach_the_last_row_and_column() {
+        // Only the bottom right cell has any power.
+        let mut grid = Grid::new(4, 3);
+        for y in 0..3 {
+            for x in 0..4 {
+                grid[(x, y)] = -1;
+            }
+        }
+        grid[(3, 2)] = 4;
+
+        assert_eq!(
+            Some((4, 3, 1, 4)),
+            find_best_square_any_size(&grid, Window::whole(&grid))
+        );
+        assert_eq!(
+            Some((3, 2, 2, 1)),
+            find_best_square_of_size(2, &grid, Window::whole(&grid))
+        );
+        assert_eq!(
+            None,
+            find_best_square_any_size(
+                &grid,
+                Window {
+                    x: 10,
+                    y: 1,
+                    width: 5,
+                    height: 5
+                }
+            )
+        );
+    }
+}
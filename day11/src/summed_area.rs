@@ -0,0 +1,35 @@
+use crate::Grid;
+
+/// Holds the sum of every rectangle of a grid which starts at the origin, so
+/// that the sum of any rectangle can be found from four of them.
+pub struct SummedAreaTable {
+    sums: Grid<i32>,
+}
+
+impl SummedAreaTable {
+    pub fn new(grid: &Grid<i32>) -> SummedAreaTable {
+        // sums[(x, y)] is the sum of the cells above and to the left of (x, y),
+        // not including column x or row y, so it needs one more row and column.
+        let mut sums = Grid::new(grid.width() + 1, grid.height() + 1);
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                sums[(x + 1, y + 1)] =
+                    grid[(x, y)] + sums[(x, y + 1)] + sums[(x + 1, y)] - sums[(x, y)];
+            }
+        }
+
+        SummedAreaTable { sums }
+    }
+
+    /// Finds the sum of the cells in the rectangle `w` cells wide and `h` cells
+    /// high whose top left cell is (x, y).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rectangle does not fit inside the grid.
+    pub fn rect_sum(&self, x: usize, y: usize, w: usize, h: usize) -> i32 {
+        let s = &self.sums;
+        s[(x + w, y + h)] - s[(x, y + h)] - s[(x + w, y)] + s[(x, y)]
+    }
+}
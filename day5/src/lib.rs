@@ -0,0 +1,95 @@
+mod polymer;
+pub use crate::polymer::Polymer;
+use std::collections::HashMap;
+
+pub fn units_react(left: char, right: char) -> bool {
+    left.is_uppercase() != right.is_uppercase() && left.eq_ignore_ascii_case(&right)
+}
+
+pub fn react(polymer: &str) -> String {
+    let mut reacted = String::new();
+
+    for original_character in polymer.chars() {
+        match reacted.chars().last() {
+            Some(reacted_character) if units_react(reacted_character, original_character) => {
+                reacted.pop();
+            }
+
+            _ => {
+                reacted.push(original_character);
+            }
+        }
+    }
+
+    reacted
+}
+
+pub fn optimize(polymer: &str) -> (char, String) {
+    let mut solutions = HashMap::with_capacity(26);
+
+    for letter in alphabet() {
+        let mut filtered = polymer.to_string();
+        filtered.retain(|c| c.to_ascii_lowercase() != letter);
+        let optimized = react(&filtered);
+        solutions.insert(letter, optimized);
+    }
+
+    let (&removed_letter, optimized) = solutions
+        .iter()
+        .min_by_key(|(_, sequence)| sequence.len())
+        .unwrap();
+
+    (removed_letter, optimized.to_owned())
+}
+
+fn alphabet() -> Alphabet {
+    Alphabet { offset: 0 }
+}
+
+struct Alphabet {
+    offset: u8,
+}
+
+impl Iterator for Alphabet {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.offset < 26 {
+            let letter = (b'a' + self.offset) as char;
+            self.offset += 1;
+
+            Some(letter)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_reactions() {
+        assert!(units_react('a', 'A'));
+        assert!(units_react('A', 'a'));
+        assert!(units_react('z', 'Z'));
+
+        assert!(!units_react('a', 'a'));
+        assert!(!units_react('A', 'A'));
+        assert!(!units_react('a', 'B'));
+    }
+
+    #[test]
+    fn example_reaction() {
+        let reacted = react("dabAcCaCBAcCcaDA");
+        assert_eq!(String::from("dabCBAcaDA"), reacted);
+    }
+
+    #[test]
+    fn example_optimization() {
+        let (removed, optimized) = optimize("dabAcCaCBAcCcaDA");
+        assert_eq!('c', removed);
+        assert_eq!("daDA".to_string(), optimized);
+    }
+}
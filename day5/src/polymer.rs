@@ -0,0 +1,658 @@
+use crate::units_react;
+
+/// The largest number of units stored in one chunk before it is split in two.
+const MAX_CHUNK_LENGTH: usize = 128;
+
+/// The modulus of the polynomial hashes, the Mersenne prime 2^61 - 1.
+const MODULUS: u64 = (1 << 61) - 1;
+
+/// The base of the polynomial hashes.
+const BASE: u64 = 0x1F3D_5B79_A2C4_E68B % MODULUS;
+
+/// A polymer that can be edited one unit at a time while keeping track of
+/// the length it would react down to.
+///
+/// The units are stored in chunks of at most `MAX_CHUNK_LENGTH`, held in order
+/// in a treap. Each node of the treap summarises the fully reacted form of its
+/// subtree without storing it: two reacted sequences can only react where they
+/// meet, so a node only records how many units cancel between its left subtree
+/// and its chunk and between those and its right subtree, together with a few
+/// polynomial hashes. The hash of any prefix of a subtree's reacted form (or of
+/// its inverse, reversed with the case of every letter swapped) can then be
+/// found by walking down one path of the subtree, and the number of units which
+/// cancel is found by binary search on those hashes.
+///
+/// Each edit changes one chunk and rebuilds the summaries along O(log n)
+/// expected paths of the treap, each taking O(log² n) time, so an edit takes
+/// O(log³ n) expected time and the polymer needs O(n) memory. The hashes could
+/// in principle collide and report a wrong length, but with a 61-bit modulus
+/// the chance is negligible.
+#[derive(Debug, Clone)]
+pub struct Polymer {
+    root: Link,
+    powers: Vec<u64>,
+    seed: u64,
+}
+
+impl Polymer {
+    pub fn new(units: &str) -> Polymer {
+        let units: Vec<char> = units.chars().collect();
+        let mut polymer = Polymer {
+            root: None,
+            powers: vec![1],
+            seed: 0x9E37_79B9_7F4A_7C15,
+        };
+        polymer.reserve_powers(units.len());
+
+        for chunk in units.chunks(MAX_CHUNK_LENGTH / 2) {
+            let leaf = polymer.leaf(chunk.to_vec());
+            polymer.root = merge(polymer.root.take(), leaf, &polymer.powers);
+        }
+
+        polymer
+    }
+
+    /// The number of units in the polymer before it reacts.
+    pub fn len(&self) -> usize {
+        unit_count(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// The number of units left after the polymer has fully reacted.
+    pub fn reduced_len(&self) -> usize {
+        reduced_len(&self.root)
+    }
+
+    /// The units left after the polymer has fully reacted.
+    pub fn reduced(&self) -> String {
+        let mut reduced = String::with_capacity(self.reduced_len());
+        collect(&self.root, 0, self.reduced_len(), &mut reduced);
+
+        reduced
+    }
+
+    /// Inserts a unit so that it ends up at the given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position > len`.
+    pub fn insert(&mut self, position: usize, unit: char) {
+        assert!(
+            position <= self.len(),
+            "Cannot insert at position {} in a polymer of length {}",
+            position,
+            self.len()
+        );
+
+        self.reserve_powers(self.len() + 1);
+        self.edit(position, |units, offset| units.insert(offset, unit));
+    }
+
+    /// Removes and returns the unit at the given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position >= len`.
+    pub fn remove(&mut self, position: usize) -> char {
+        assert!(
+            position < self.len(),
+            "Cannot remove position {} from a polymer of length {}",
+            position,
+            self.len()
+        );
+
+        self.edit(position, |units, offset| units.remove(offset))
+    }
+
+    /// Takes the chunk holding the given position out of the treap, changes its
+    /// units and puts it back, splitting it if it has grown too long and leaving
+    /// it out if it is empty.
+    fn edit<T>(&mut self, position: usize, change: impl FnOnce(&mut Vec<char>, usize) -> T) -> T {
+        let (index, offset) = self.locate(position);
+        let (before, rest) = split(self.root.take(), index, &self.powers);
+        let (chunk, after) = split(rest, 1, &self.powers);
+
+        let mut units = chunk.map_or_else(Vec::new, |node| node.units);
+        let result = change(&mut units, offset);
+
+        let middle = if units.len() > MAX_CHUNK_LENGTH {
+            let tail = units.split_off(units.len() / 2);
+            let head = self.leaf(units);
+            let tail = self.leaf(tail);
+            merge(head, tail, &self.powers)
+        } else if units.is_empty() {
+            None
+        } else {
+            self.leaf(units)
+        };
+
+        let start = merge(before, middle, &self.powers);
+        self.root = merge(start, after, &self.powers);
+
+        result
+    }
+
+    /// Finds the index of the chunk containing the given position, and the offset
+    /// within that chunk. A position just past the end of the polymer maps to the
+    /// end of the last chunk.
+    fn locate(&self, mut position: usize) -> (usize, usize) {
+        let mut link = &self.root;
+        let mut chunks_before = 0;
+
+        while let Some(node) = link {
+            let left_units = unit_count(&node.left);
+            if position < left_units {
+                link = &node.left;
+                continue;
+            }
+
+            position -= left_units;
+            chunks_before += chunk_count(&node.left);
+            if position < node.units.len() || node.right.is_none() {
+                return (chunks_before, position);
+            }
+
+            position -= node.units.len();
+            chunks_before += 1;
+            link = &node.right;
+        }
+
+        (0, 0)
+    }
+
+    fn leaf(&mut self, units: Vec<char>) -> Link {
+        // xorshift64*, which is plenty for balancing the treap.
+        self.seed ^= self.seed >> 12;
+        self.seed ^= self.seed << 25;
+        self.seed ^= self.seed >> 27;
+        let priority = self.seed.wrapping_mul(0x2545_F491_4F6C_DD1D);
+
+        let mut node = Box::new(Node::new(units, priority));
+        node.update(&self.powers);
+
+        Some(node)
+    }
+
+    /// Makes sure that powers of the base are available for sequences up to the
+    /// given length.
+    fn reserve_powers(&mut self, length: usize) {
+        while self.powers.len() <= length {
+            let next = multiply(*self.powers.last().unwrap(), BASE);
+            self.powers.push(next);
+        }
+    }
+}
+
+type Link = Option<Box<Node>>;
+
+/// One chunk of units, and the root of the subtree of chunks around it.
+#[derive(Debug, Clone)]
+struct Node {
+    units: Vec<char>,
+    /// The chunk's units after reacting.
+    reduced: Vec<char>,
+    /// The hash of each prefix of `reduced`.
+    forward: Vec<u64>,
+    /// The hash of each prefix of the inverse of `reduced`.
+    inverse: Vec<u64>,
+    priority: u64,
+    left: Link,
+    right: Link,
+    chunk_count: usize,
+    unit_count: usize,
+    summary: Summary,
+}
+
+/// How the reacted form of a subtree is put together from its parts.
+///
+/// The middle part is the reacted left subtree followed by the reacted chunk,
+/// with `inner` units cancelled from each. The whole is the middle followed by
+/// the reacted right subtree, with `outer` units cancelled from each. The hashes
+/// are of the parts which are needed to find the hashes of prefixes of the whole.
+#[derive(Debug, Clone, Copy, Default)]
+struct Summary {
+    inner: usize,
+    outer: usize,
+    middle_len: usize,
+    len: usize,
+    /// The part of the left subtree which is kept.
+    left_kept: u64,
+    /// The start of the inverse of the left subtree, which cancels.
+    left_cut_inverse: u64,
+    /// The part of the middle which is kept.
+    middle_kept: u64,
+    /// The start of the right subtree, which cancels.
+    right_cut: u64,
+    /// The part of the inverse of the right subtree which is kept.
+    right_kept_inverse: u64,
+    /// The start of the inverse of the middle, which cancels.
+    middle_cut_inverse: u64,
+}
+
+impl Node {
+    fn new(units: Vec<char>, priority: u64) -> Node {
+        let reduced = reduce(&units);
+        let mut forward = vec![0];
+        let mut inverse = vec![0];
+        for (&unit, &opposite) in reduced.iter().zip(reduced.iter().rev()) {
+            forward.push(add(
+                multiply(*forward.last().unwrap(), BASE),
+                unit_value(unit),
+            ));
+            inverse.push(add(
+                multiply(*inverse.last().unwrap(), BASE),
+                inverse_value(opposite),
+            ));
+        }
+
+        Node {
+            unit_count: units.len(),
+            units,
+            reduced,
+            forward,
+            inverse,
+            priority,
+            left: None,
+            right: None,
+            chunk_count: 1,
+            summary: Summary::default(),
+        }
+    }
+
+    /// Recalculates the summary of the subtree after either child has changed.
+    fn update(&mut self, powers: &[u64]) {
+        self.chunk_count = chunk_count(&self.left) + 1 + chunk_count(&self.right);
+        self.unit_count = unit_count(&self.left) + self.units.len() + unit_count(&self.right);
+
+        let left_len = reduced_len(&self.left);
+        let right_len = reduced_len(&self.right);
+
+        let inner = longest_match(left_len.min(self.reduced.len()), |length| {
+            inverse_prefix_hash(&self.left, length, powers) == self.forward[length]
+        });
+        self.summary.inner = inner;
+        self.summary.left_kept = prefix_hash(&self.left, left_len - inner, powers);
+        self.summary.left_cut_inverse = inverse_prefix_hash(&self.left, inner, powers);
+
+        let middle_len = left_len + self.reduced.len() - 2 * inner;
+        self.summary.middle_len = middle_len;
+
+        let outer = longest_match(middle_len.min(right_len), |length| {
+            self.middle_inverse_prefix_hash(length, powers)
+                == prefix_hash(&self.right, length, powers)
+        });
+        self.summary.outer = outer;
+        self.summary.middle_kept = self.middle_prefix_hash(middle_len - outer, powers);
+        self.summary.right_cut = prefix_hash(&self.right, outer, powers);
+        self.summary.right_kept_inverse =
+            inverse_prefix_hash(&self.right, right_len - outer, powers);
+        self.summary.middle_cut_inverse = self.middle_inverse_prefix_hash(outer, powers);
+        self.summary.len = middle_len + right_len - 2 * outer;
+    }
+
+    /// The hash of the first `length` units of the reacted subtree.
+    fn prefix_hash(&self, length: usize, powers: &[u64]) -> u64 {
+        let summary = &self.summary;
+        let middle_kept = summary.middle_len - summary.outer;
+
+        if length <= middle_kept {
+            self.middle_prefix_hash(length, powers)
+        } else {
+            let extra = length - middle_kept;
+            let right = prefix_hash(&self.right, summary.outer + extra, powers);
+            let right = remove_prefix(right, summary.right_cut, extra, powers);
+            concatenate(summary.middle_kept, right, extra, powers)
+        }
+    }
+
+    fn middle_prefix_hash(&self, length: usize, powers: &[u64]) -> u64 {
+        let summary = &self.summary;
+        let left_kept = reduced_len(&self.left) - summary.inner;
+
+        if length <= left_kept {
+            prefix_hash(&self.left, length, powers)
+        } else {
+            let extra = length - left_kept;
+            let chunk = remove_prefix(
+                self.forward[summary.inner + extra],
+                self.forward[summary.inner],
+                extra,
+                powers,
+            );
+            concatenate(summary.left_kept, chunk, extra, powers)
+        }
+    }
+
+    /// The hash of the first `length` units of the inverse of the reacted subtree,
+    /// which is made of the kept part of the inverse of the right subtree followed
+    /// by the kept part of the inverse of the middle.
+    fn inverse_prefix_hash(&self, length: usize, powers: &[u64]) -> u64 {
+        let summary = &self.summary;
+        let right_kept = reduced_len(&self.right) - summary.outer;
+
+        if length <= right_kept {
+            inverse_prefix_hash(&self.right, length, powers)
+        } else {
+            let extra = length - right_kept;
+            let middle = self.middle_inverse_prefix_hash(summary.outer + extra, powers);
+            let middle = remove_prefix(middle, summary.middle_cut_inverse, extra, powers);
+            concatenate(summary.right_kept_inverse, middle, extra, powers)
+        }
+    }
+
+    fn middle_inverse_prefix_hash(&self, length: usize, powers: &[u64]) -> u64 {
+        let summary = &self.summary;
+        let chunk_kept = self.reduced.len() - summary.inner;
+
+        if length <= chunk_kept {
+            self.inverse[length]
+        } else {
+            let extra = length - chunk_kept;
+            let left = inverse_prefix_hash(&self.left, summary.inner + extra, powers);
+            let left = remove_prefix(left, summary.left_cut_inverse, extra, powers);
+            concatenate(self.inverse[chunk_kept], left, extra, powers)
+        }
+    }
+
+    /// Appends units `start..end` of the reacted subtree to `out`.
+    fn collect(&self, start: usize, end: usize, out: &mut String) {
+        let summary = &self.summary;
+        let middle_kept = summary.middle_len - summary.outer;
+
+        if start < middle_kept {
+            self.collect_middle(start, end.min(middle_kept), out);
+        }
+        if end > middle_kept {
+            let from = summary.outer + start.max(middle_kept) - middle_kept;
+            collect(&self.right, from, summary.outer + end - middle_kept, out);
+        }
+    }
+
+    fn collect_middle(&self, start: usize, end: usize, out: &mut String) {
+        let inner = self.summary.inner;
+        let left_kept = reduced_len(&self.left) - inner;
+
+        if start < left_kept {
+            collect(&self.left, start, end.min(left_kept), out);
+        }
+        if end > left_kept {
+            let from = inner + start.max(left_kept) - left_kept;
+            out.extend(&self.reduced[from..inner + end - left_kept]);
+        }
+    }
+}
+
+fn chunk_count(link: &Link) -> usize {
+    link.as_ref().map_or(0, |node| node.chunk_count)
+}
+
+fn unit_count(link: &Link) -> usize {
+    link.as_ref().map_or(0, |node| node.unit_count)
+}
+
+fn reduced_len(link: &Link) -> usize {
+    link.as_ref().map_or(0, |node| node.summary.len)
+}
+
+fn prefix_hash(link: &Link, length: usize, powers: &[u64]) -> u64 {
+    link.as_ref()
+        .map_or(0, |node| node.prefix_hash(length, powers))
+}
+
+fn inverse_prefix_hash(link: &Link, length: usize, powers: &[u64]) -> u64 {
+    link.as_ref()
+        .map_or(0, |node| node.inverse_prefix_hash(length, powers))
+}
+
+fn collect(link: &Link, start: usize, end: usize, out: &mut String) {
+    if let Some(node) = link {
+        if start < end {
+            node.collect(start, end, out);
+        }
+    }
+}
+
+/// Joins two treaps, with every chunk of `left` coming before every chunk of `right`.
+fn merge(left: Link, right: Link, powers: &[u64]) -> Link {
+    match (left, right) {
+        (None, right) => right,
+        (left, None) => left,
+        (Some(mut left), Some(mut right)) => {
+            if left.priority > right.priority {
+                left.right = merge(left.right.take(), Some(right), powers);
+                left.update(powers);
+                Some(left)
+            } else {
+                right.left = merge(Some(left), right.left.take(), powers);
+                right.update(powers);
+                Some(right)
+            }
+        }
+    }
+}
+
+/// Splits a treap into its first `count` chunks and the rest.
+fn split(link: Link, count: usize, powers: &[u64]) -> (Link, Link) {
+    match link {
+        None => (None, None),
+        Some(mut node) => {
+            let left_chunks = chunk_count(&node.left);
+            if count <= left_chunks {
+                let (before, after) = split(node.left.take(), count, powers);
+                node.left = after;
+                node.update(powers);
+                (before, Some(node))
+            } else {
+                let (before, after) = split(node.right.take(), count - left_chunks - 1, powers);
+                node.right = before;
+                node.update(powers);
+                (Some(node), after)
+            }
+        }
+    }
+}
+
+/// Finds the largest length up to `limit` for which `matches` is true, given
+/// that it is true for every length up to some point and false after it.
+fn longest_match(limit: usize, matches: impl Fn(usize) -> bool) -> usize {
+    let (mut low, mut high) = (0, limit);
+
+    while low < high {
+        let middle = low + (high - low).div_ceil(2);
+        if matches(middle) {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+
+    low
+}
+
+fn unit_value(unit: char) -> u64 {
+    u64::from(unit) + 1
+}
+
+/// The value of the unit which reacts with `unit`. Units which cannot react
+/// are given a value which no unit has.
+fn inverse_value(unit: char) -> u64 {
+    if unit.is_ascii_lowercase() {
+        unit_value(unit.to_ascii_uppercase())
+    } else if unit.is_ascii_uppercase() {
+        unit_value(unit.to_ascii_lowercase())
+    } else {
+        u64::from(unit) + 1 + (1 << 32)
+    }
+}
+
+fn add(a: u64, b: u64) -> u64 {
+    let sum = a + b;
+    if sum >= MODULUS {
+        sum - MODULUS
+    } else {
+        sum
+    }
+}
+
+fn multiply(a: u64, b: u64) -> u64 {
+    let product = u128::from(a) * u128::from(b);
+    let folded = (product >> 61) as u64 + (product as u64 & MODULUS);
+    if folded >= MODULUS {
+        folded - MODULUS
+    } else {
+        folded
+    }
+}
+
+/// The hash of two sequences one after the other, given the length of the second.
+fn concatenate(first: u64, second: u64, second_len: usize, powers: &[u64]) -> u64 {
+    add(multiply(first, powers[second_len]), second)
+}
+
+/// The hash of the last `rest_len` units of a sequence, given the hash of the
+/// whole sequence and of the units before them.
+fn remove_prefix(whole: u64, prefix: u64, rest_len: usize, powers: &[u64]) -> u64 {
+    add(whole, MODULUS - multiply(prefix, powers[rest_len]))
+}
+
+/// Fully reacts a sequence of units.
+fn reduce(units: &[char]) -> Vec<char> {
+    let mut reduced: Vec<char> = Vec::with_capacity(units.len());
+
+    for &unit in units {
+        match reduced.last() {
+            Some(&previous) if units_react(previous, unit) => {
+                reduced.pop();
+            }
+
+            _ => {
+                reduced.push(unit);
+            }
+        }
+    }
+
+    reduced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::react;
+
+    #[test]
+    fn example_polymer() {
+        let polymer = Polymer::new("dabAcCaCBAcCcaDA");
+
+        assert_eq!(16, polymer.len());
+        assert_eq!(10, polymer.reduced_len());
+        assert_eq!("dabCBAcaDA".to_string(), polymer.reduced());
+    }
+
+    #[test]
+    fn empty_polymer() {
+        let mut polymer = Polymer::new("");
+        assert!(polymer.is_empty());
+        assert_eq!(0, polymer.reduced_len());
+
+        polymer.insert(0, 'a');
+        polymer.insert(1, 'A');
+        assert_eq!(2, polymer.len());
+        assert_eq!(0, polymer.reduced_len());
+    }
+
+    #[test]
+    fn insert_and_remove_units() {
+        let mut polymer = Polymer::new("dabAcCaCBAcCcaDA");
+
+        // dabAcCaCBAcCcaDA -> dabAcCaCBAcCcaDAd
+        polymer.insert(16, 'd');
+        assert_eq!(react("dabAcCaCBAcCcaDAd"), polymer.reduced());
+
+        // dabAcCaCBAcCcaDAd -> abAcCaCBAcCcaDAd
+        assert_eq!('d', polymer.remove(0));
+        assert_eq!(react("abAcCaCBAcCcaDAd"), polymer.reduced());
+
+        // abAcCaCBAcCcaDAd -> abAcCaCBAcCcaADAd
+        polymer.insert(13, 'A');
+        assert_eq!(react("abAcCaCBAcCcaADAd"), polymer.reduced());
+    }
+
+    #[test]
+    fn many_edits_match_react() {
+        let letters = ['a', 'A', 'b', 'B', 'c', 'C'];
+        let mut seed: u64 = 12345;
+        let mut next_random = move |limit: usize| {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            ((seed >> 33) as usize) % limit
+        };
+
+        let mut units: Vec<char> = (0..1000)
+            .map(|_| letters[next_random(letters.len())])
+            .collect();
+        let mut polymer = Polymer::new(&units.iter().collect::<String>());
+
+        for _ in 0..2000 {
+            if units.is_empty() || next_random(3) > 0 {
+                let position = next_random(units.len() + 1);
+                let unit = letters[next_random(letters.len())];
+                units.insert(position, unit);
+                polymer.insert(position, unit);
+            } else {
+                let position = next_random(units.len());
+                assert_eq!(units.remove(position), polymer.remove(position));
+            }
+
+            let expected = react(&units.iter().collect::<String>());
+            assert_eq!(units.len(), polymer.len());
+            assert_eq!(expected, polymer.reduced());
+        }
+    }
+
+    #[test]
+    fn repeated_inserts_split_chunks() {
+        let mut polymer = Polymer::new("xy");
+        let mut expected = String::from("xy");
+
+        for n in 0..1000 {
+            let unit = if n % 3 == 0 { 'q' } else { 'Q' };
+            polymer.insert(1, unit);
+            expected.insert(1, unit);
+        }
+
+        assert_eq!(1002, polymer.len());
+        assert_eq!(react(&expected), polymer.reduced());
+
+        for _ in 0..1000 {
+            polymer.remove(1);
+        }
+        assert_eq!("xy", polymer.reduced());
+    }
+
+    #[test]
+    fn edits_to_a_long_polymer() {
+        // Every unit of "abab...ab" cancels with one of "BABA...BA".
+        let half = 25_000;
+        let units = "ab".repeat(half) + &"BA".repeat(half);
+        let mut polymer = Polymer::new(&units);
+        assert_eq!(0, polymer.reduced_len());
+
+        // A unit in the middle stops the two halves from reacting at all.
+        polymer.insert(2 * half, 'c');
+        assert_eq!(4 * half + 1, polymer.reduced_len());
+
+        // Removing the first unit leaves an unmatched 'A' at the end.
+        polymer.remove(2 * half);
+        polymer.remove(0);
+        assert_eq!(1, polymer.reduced_len());
+        assert_eq!("A", polymer.reduced());
+
+        for n in 0..1000 {
+            polymer.insert(half + n, 'c');
+            polymer.insert(half + n + 1, 'C');
+        }
+        assert_eq!(1, polymer.reduced_len());
+    }
+}
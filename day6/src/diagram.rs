@@ -0,0 +1,232 @@
+use crate::{AreaMap, AreaMapError, Closest, Point};
+use rgb::RGBA8;
+use std::{collections::HashSet, error, fmt, path::Path};
+
+const BLACK: RGBA8 = RGBA8 {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 255,
+};
+const GREY: RGBA8 = RGBA8 {
+    r: 128,
+    g: 128,
+    b: 128,
+    a: 255,
+};
+const WHITE: RGBA8 = RGBA8 {
+    r: 255,
+    g: 255,
+    b: 255,
+    a: 255,
+};
+
+#[derive(Debug)]
+pub enum DiagramError {
+    Map(AreaMapError),
+    Encoding(lodepng::Error),
+}
+
+impl fmt::Display for DiagramError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiagramError::Map(error) => write!(f, "Cannot draw the diagram: {}", error),
+            DiagramError::Encoding(error) => write!(f, "Cannot save the diagram: {}", error),
+        }
+    }
+}
+
+impl error::Error for DiagramError {}
+
+impl From<AreaMapError> for DiagramError {
+    fn from(error: AreaMapError) -> DiagramError {
+        DiagramError::Map(error)
+    }
+}
+
+impl From<lodepng::Error> for DiagramError {
+    fn from(error: lodepng::Error) -> DiagramError {
+        DiagramError::Encoding(error)
+    }
+}
+
+/// Draws the area closest to each point in its own colour and saves it as a PNG.
+///
+/// Areas which carry on forever are drawn in a darker shade, cells which are equally
+/// close to two or more points are grey and the points themselves are black. If a
+/// `safe_distance` is given, cells whose total distance to all of the points is less
+/// than it are lightened, and the image is made large enough to hold all of them.
+pub fn save_diagram<P: AsRef<Path>>(
+    points: &[Point],
+    safe_distance: Option<i32>,
+    filename: P,
+) -> Result<(), DiagramError> {
+    let map = AreaMap::new(points)?;
+    let (map_top_left, map_bottom_right) = map.extent();
+    let ((mut left, mut top), (mut right, mut bottom)) = (map_top_left, map_bottom_right);
+
+    if let Some(limit) = safe_distance {
+        let xs: Vec<i32> = points.iter().map(|p| p.0).collect();
+        let ys: Vec<i32> = points.iter().map(|p| p.1).collect();
+        let limit = i64::from(limit);
+
+        // A column can only hold part of the region if its distance along the x
+        // axis plus the smallest distance along the y axis is under the limit.
+        let (l, r) = region_span(&xs, (left, right), limit - smallest_axis_total(&ys));
+        let (t, b) = region_span(&ys, (top, bottom), limit - smallest_axis_total(&xs));
+        left = l;
+        right = r;
+        top = t;
+        bottom = b;
+    }
+
+    let width = (right - left + 1) as usize;
+    let height = (bottom - top + 1) as usize;
+
+    let colours: Vec<RGBA8> = map
+        .areas()
+        .iter()
+        .enumerate()
+        .map(|(number, area)| {
+            let colour = area_colour(number);
+            if area.infinite {
+                blend(colour, BLACK, 0.5)
+            } else {
+                colour
+            }
+        })
+        .collect();
+
+    let column_totals: Vec<i64> = (left..=right)
+        .map(|x| axis_total(points.iter().map(|p| p.0), x))
+        .collect();
+    let row_totals: Vec<i64> = (top..=bottom)
+        .map(|y| axis_total(points.iter().map(|p| p.1), y))
+        .collect();
+
+    let point_set: HashSet<Point> = points.iter().cloned().collect();
+    let mut image = Vec::with_capacity(width * height);
+
+    for (row, y) in (top..=bottom).enumerate() {
+        for (column, x) in (left..=right).enumerate() {
+            // Beyond the labelled cells every distance grows by the same amount,
+            // so the closest point is the same as at the nearest labelled cell.
+            let labelled = (
+                x.clamp(map_top_left.0, map_bottom_right.0),
+                y.clamp(map_top_left.1, map_bottom_right.1),
+            );
+            let mut pixel = match map.closest(labelled) {
+                Some(Closest::Point(number)) => colours[number],
+                _ => GREY,
+            };
+
+            if let Some(limit) = safe_distance {
+                if column_totals[column] + row_totals[row] < i64::from(limit) {
+                    pixel = blend(pixel, WHITE, 0.5);
+                }
+            }
+
+            if point_set.contains(&(x, y)) {
+                pixel = BLACK;
+            }
+
+            image.push(pixel);
+        }
+    }
+
+    lodepng::encode32_file(filename, &image, width, height)?;
+
+    Ok(())
+}
+
+/// The total distance along one axis from `position` to each of the coordinates.
+fn axis_total(coordinates: impl Iterator<Item = i32>, position: i32) -> i64 {
+    coordinates
+        .map(|c| (i64::from(c) - i64::from(position)).abs())
+        .sum()
+}
+
+/// The smallest total distance along one axis, which is found at the median.
+fn smallest_axis_total(coordinates: &[i32]) -> i64 {
+    let mut sorted = coordinates.to_vec();
+    sorted.sort_unstable();
+    let median = sorted[sorted.len() / 2];
+
+    axis_total(sorted.into_iter(), median)
+}
+
+/// Widens the span `(low, high)` to take in every position along the axis whose
+/// total distance to the coordinates is less than `limit`. The total distance
+/// only grows further from the points, so the span is widened one step at a time
+/// until the next position is too far away.
+fn region_span(coordinates: &[i32], (mut low, mut high): (i32, i32), limit: i64) -> (i32, i32) {
+    while axis_total(coordinates.iter().cloned(), low - 1) < limit {
+        low -= 1;
+    }
+    while axis_total(coordinates.iter().cloned(), high + 1) < limit {
+        high += 1;
+    }
+
+    (low, high)
+}
+
+/// Picks a bright colour for each point by stepping around the colour wheel
+/// by the golden ratio, so that neighbouring numbers get very different hues.
+fn area_colour(number: usize) -> RGBA8 {
+    let hue = (number as f64 * 0.618_033_988_749_895).fract() * 6.0;
+    let rising = (255.0 * hue.fract()) as u8;
+    let falling = 255 - rising;
+
+    match hue as u8 {
+        0 => RGBA8::new(255, rising, 0, 255),
+        1 => RGBA8::new(falling, 255, 0, 255),
+        2 => RGBA8::new(0, 255, rising, 255),
+        3 => RGBA8::new(0, falling, 255, 255),
+        4 => RGBA8::new(rising, 0, 255, 255),
+        _ => RGBA8::new(255, 0, falling, 255),
+    }
+}
+
+/// Mixes `amount` of the `other` colour into `colour`.
+fn blend(colour: RGBA8, other: RGBA8, amount: f64) -> RGBA8 {
+    let mix = |a: u8, b: u8| (f64::from(a) * (1.0 - amount) + f64::from(b) * amount).round() as u8;
+
+    RGBA8::new(
+        mix(colour.r, other.r),
+        mix(colour.g, other.g),
+        mix(colour.b, other.b),
+        255,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn area_colours_are_distinct() {
+        let colours: HashSet<(u8, u8, u8)> =
+            (0..50).map(area_colour).map(|c| (c.r, c.g, c.b)).collect();
+
+        assert_eq!(50, colours.len());
+    }
+
+    #[test]
+    fn region_span_reaches_past_the_points() {
+        // Totals along the axis: 5 from 0 to 5, then 7 at -1 and 6, 9 at -2 and 7.
+        let coordinates = [0, 5];
+
+        assert_eq!((-2, 7), region_span(&coordinates, (-1, 6), 10));
+        assert_eq!((-1, 6), region_span(&coordinates, (-1, 6), 5));
+        assert_eq!(5, smallest_axis_total(&coordinates));
+    }
+
+    #[test]
+    fn test_blend() {
+        assert_eq!(RGBA8::new(128, 128, 128, 255), blend(BLACK, WHITE, 0.5));
+        assert_eq!(
+            RGBA8::new(255, 0, 0, 255),
+            blend(RGBA8::new(255, 0, 0, 255), BLACK, 0.0)
+        );
+    }
+}
@@ -1,67 +0,0 @@
-lazy_static! {
-    pub static ref PUZZLE_INPUT: Vec<(i32, i32)> = {
-        vec![
-            (227, 133),
-            (140, 168),
-            (99, 112),
-            (318, 95),
-            (219, 266),
-            (134, 144),
-            (306, 301),
-            (189, 188),
-            (58, 334),
-            (337, 117),
-            (255, 73),
-            (245, 144),
-            (102, 257),
-            (255, 353),
-            (303, 216),
-            (141, 167),
-            (40, 321),
-            (201, 50),
-            (60, 188),
-            (132, 74),
-            (125, 199),
-            (176, 307),
-            (204, 218),
-            (338, 323),
-            (276, 278),
-            (292, 229),
-            (109, 228),
-            (85, 305),
-            (86, 343),
-            (97, 254),
-            (182, 151),
-            (110, 292),
-            (285, 124),
-            (43, 223),
-            (153, 188),
-            (285, 136),
-            (334, 203),
-            (84, 243),
-            (92, 185),
-            (330, 223),
-            (259, 275),
-            (106, 199),
-            (183, 205),
-            (188, 212),
-            (231, 150),
-            (158, 95),
-            (174, 212),
-            (279, 97),
-            (172, 131),
-            (247, 320),
-        ]
-    };
-
-    pub static ref EXAMPLE_INPUT: Vec<(i32, i32)> = {
-        vec![
-            (1, 1),
-            (1, 6),
-            (8, 3),
-            (3, 4),
-            (5, 5),
-            (8, 9),
-        ]
-    };
-}
\ No newline at end of file
@@ -0,0 +1,234 @@
+mod diagram;
+mod parsing;
+mod voronoi;
+
+pub use crate::diagram::{save_diagram, DiagramError};
+pub use crate::parsing::{parse_points, ParseError};
+pub use crate::voronoi::{Area, AreaMap, AreaMapError, Closest, MAX_SPAN};
+
+pub type Point = (i32, i32);
+
+fn bounds(points: &[Point]) -> (Point, Point) {
+    assert!(!points.is_empty());
+
+    let mut x_min = points[0].0;
+    let mut x_max = x_min;
+    let mut y_min = points[0].1;
+    let mut y_max = y_min;
+
+    for (x, y) in points {
+        if *x < x_min {
+            x_min = *x;
+        }
+
+        if *x > x_max {
+            x_max = *x;
+        }
+
+        if *y < y_min {
+            y_min = *y;
+        }
+
+        if *y > y_max {
+            y_max = *y;
+        }
+    }
+
+    ((x_min, y_min), (x_max, y_max))
+}
+
+/// Finds the size of the largest area that is closest to one of the points and
+/// does not carry on forever. Returns an error if the points are too far apart
+/// to map, and panics if there are no points.
+pub fn find_largest_area(points: &[Point]) -> Result<usize, AreaMapError> {
+    Ok(AreaMap::new(points)?
+        .areas()
+        .iter()
+        .filter(|area| !area.infinite)
+        .map(|area| area.size)
+        .max()
+        .unwrap_or(0))
+}
+
+/// Counts the positions whose total Manhattan distance to all of the points is
+/// less than `max_distance`.
+///
+/// Manhattan distance is separable, so the total distance from (x, y) is the sum of
+/// the total distance along the x axis from x and along the y axis from y. The
+/// region size is therefore the number of pairs of axis totals that add up to
+/// less than `max_distance`, which can be counted with one sweep over each axis.
+pub fn part2_region_size(points: &[Point], max_distance: i32) -> usize {
+    let limit = i64::from(max_distance);
+    let x_sums = axis_distance_sums(points.iter().map(|p| i64::from(p.0)).collect(), limit);
+    let y_sums = axis_distance_sums(points.iter().map(|p| i64::from(p.1)).collect(), limit);
+
+    let mut region_size = 0;
+    let mut y_count = y_sums.len();
+
+    for x_sum in x_sums {
+        while y_count > 0 && x_sum + y_sums[y_count - 1] >= limit {
+            y_count -= 1;
+        }
+
+        region_size += y_count;
+    }
+
+    region_size
+}
+
+/// Finds the total distance to all of the given coordinates from every position
+/// along one axis where it is less than `limit`, sorted into ascending order.
+fn axis_distance_sums(mut coordinates: Vec<i64>, limit: i64) -> Vec<i64> {
+    coordinates.sort_unstable();
+
+    let count = coordinates.len() as i64;
+    let median = coordinates[coordinates.len() / 2];
+    let sum_at_median: i64 = coordinates.iter().map(|c| (c - median).abs()).sum();
+    let mut sums = Vec::new();
+
+    // Moving one step to the right takes us one step further away from every
+    // coordinate at or to the left of x, and one step closer to all of the others.
+    let mut x = median;
+    let mut sum = sum_at_median;
+    while sum < limit {
+        sums.push(sum);
+        let at_or_left = coordinates.partition_point(|&c| c <= x) as i64;
+        sum += at_or_left - (count - at_or_left);
+        x += 1;
+    }
+
+    // Likewise moving to the left, starting from the position next to the median.
+    x = median;
+    sum = sum_at_median;
+    loop {
+        let left = coordinates.partition_point(|&c| c < x) as i64;
+        sum += (count - left) - left;
+        x -= 1;
+
+        if sum >= limit {
+            break;
+        }
+
+        sums.push(sum);
+    }
+
+    sums.sort_unstable();
+    sums
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_points() -> Vec<Point> {
+        parse_points(include_str!("example_input.txt")).unwrap()
+    }
+
+    /// Calculates the distance between two points in "Manhattan" geometry.
+    fn manhattan(from: Point, to: Point) -> i32 {
+        i32::abs(to.0 - from.0) + i32::abs(to.1 - from.1)
+    }
+
+    fn brute_force_region_size(points: &[Point], max_distance: i32) -> usize {
+        let ((left, top), (right, bottom)) = bounds(points);
+        let mut region_size = 0;
+
+        for x in (left - max_distance)..=(right + max_distance) {
+            for y in (top - max_distance)..=(bottom + max_distance) {
+                let total: i32 = points.iter().map(|&p| manhattan((x, y), p)).sum();
+                if total < max_distance {
+                    region_size += 1;
+                }
+            }
+        }
+
+        region_size
+    }
+
+    #[test]
+    fn test_find_closest() {
+        let map = AreaMap::new(&example_points()).unwrap();
+        assert_eq!(Some(Closest::Point(0)), map.closest((0, 0)));
+
+        assert_eq!(Some(Closest::Tie), map.closest((1, 4)));
+    }
+
+    #[test]
+    fn example_areas() {
+        let map = AreaMap::new(&example_points()).unwrap();
+        let areas = map.areas();
+
+        assert!(areas[0].infinite);
+        assert!(areas[1].infinite);
+        assert!(areas[2].infinite);
+        assert_eq!(Area { size: 9, infinite: false }, areas[3]);
+        assert_eq!(Area { size: 17, infinite: false }, areas[4]);
+        assert!(areas[5].infinite);
+    }
+
+    #[test]
+    fn areas_match_brute_force() {
+        let points: Vec<Point> = (0..200).map(|n| ((n * 37) % 101, (n * 53) % 89)).collect();
+        let map = AreaMap::new(&points).unwrap();
+        let ((left, top), (right, bottom)) = bounds(&points);
+
+        for x in left - 1..=right + 1 {
+            for y in top - 1..=bottom + 1 {
+                let distances: Vec<i32> = points.iter().map(|&p| manhattan((x, y), p)).collect();
+                let shortest = *distances.iter().min().unwrap();
+                let closest: Vec<usize> = (0..points.len()).filter(|&n| distances[n] == shortest).collect();
+                let expected = if closest.len() == 1 {
+                    Closest::Point(closest[0])
+                } else {
+                    Closest::Tie
+                };
+
+                assert_eq!(Some(expected), map.closest((x, y)));
+            }
+        }
+    }
+
+    #[test]
+    fn largest_area_part_1_example() {
+        assert_eq!(Ok(17), find_largest_area(&example_points()));
+    }
+
+    #[test]
+    fn points_too_far_apart_to_map() {
+        let points = parse_points("0, 0\n3000, 0\n0, 3000").unwrap();
+
+        assert_eq!(
+            Err(AreaMapError::TooLarge {
+                width: 3001,
+                height: 3001
+            }),
+            find_largest_area(&points)
+        );
+        // Only (0, 0) is within 6,000 of all three points.
+        assert_eq!(1, part2_region_size(&points, 6001));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(16, part2_region_size(&example_points(), 32));
+    }
+
+    #[test]
+    fn region_size_matches_brute_force() {
+        let points: Vec<Point> = (0..8).map(|n| ((n * 37) % 41, (n * 53) % 29)).collect();
+
+        for &max_distance in &[1, 140, 160, 200, 300] {
+            assert_eq!(
+                brute_force_region_size(&points, max_distance),
+                part2_region_size(&points, max_distance)
+            );
+        }
+    }
+
+    #[test]
+    fn single_point_region_size() {
+        // A diamond of radius r contains 2r(r + 1) + 1 cells.
+        let r = 249_999;
+        assert_eq!(2 * r * (r + 1) + 1, part2_region_size(&[(5, -3)], r as i32 + 1));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::Point;
+use std::{error, fmt};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseError {
+    IncorrectFormat { line: usize },
+    InvalidNumber { line: usize },
+    NoPoints,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::IncorrectFormat { line } => {
+                write!(
+                    f,
+                    "Line {}: expected coordinates in the form \"x, y\"",
+                    line
+                )
+            }
+            ParseError::InvalidNumber { line } => write!(f, "Line {}: invalid number", line),
+            ParseError::NoPoints => write!(f, "The input does not contain any coordinates"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Parses a list of coordinates with one `x, y` pair per line.
+/// Blank lines are ignored, and line numbers in errors count from 1.
+pub fn parse_points(input: &str) -> Result<Vec<Point>, ParseError> {
+    let mut points = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        if !line.trim().is_empty() {
+            points.push(parse_line(line, index + 1)?);
+        }
+    }
+
+    if points.is_empty() {
+        Err(ParseError::NoPoints)
+    } else {
+        Ok(points)
+    }
+}
+
+fn parse_line(text: &str, line: usize) -> Result<Point, ParseError> {
+    let mut parts = text.split(',');
+
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(x), Some(y), None) => {
+            let x = x
+                .trim()
+                .parse()
+                .map_err(|_| ParseError::InvalidNumber { line })?;
+            let y = y
+                .trim()
+                .parse()
+                .map_err(|_| ParseError::InvalidNumber { line })?;
+
+            Ok((x, y))
+        }
+        _ => Err(ParseError::IncorrectFormat { line }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        let points = parse_points("1, 1\n-1,6\n\n  8 , 3  \n").unwrap();
+        assert_eq!(vec![(1, 1), (-1, 6), (8, 3)], points);
+    }
+
+    #[test]
+    fn parse_errors_report_line_numbers() {
+        assert_eq!(
+            Err(ParseError::IncorrectFormat { line: 2 }),
+            parse_points("1, 1\n1 6\n")
+        );
+        assert_eq!(
+            Err(ParseError::IncorrectFormat { line: 1 }),
+            parse_points("1, 2, 3")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidNumber { line: 3 }),
+            parse_points("1, 1\n\nx, 6\n")
+        );
+        assert_eq!(Err(ParseError::NoPoints), parse_points("\n\n"));
+    }
+}
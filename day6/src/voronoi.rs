@@ -0,0 +1,177 @@
+use crate::{bounds, Point};
+use std::{collections::VecDeque, error, fmt};
+
+/// The largest number of cells that an `AreaMap` covers along either axis.
+pub const MAX_SPAN: i64 = 2_000;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AreaMapError {
+    TooLarge { width: i64, height: i64 },
+}
+
+impl fmt::Display for AreaMapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AreaMapError::TooLarge { width, height } => write!(
+                f,
+                "The points cover an area {} by {}, but at most {} by {} can be mapped",
+                width, height, MAX_SPAN, MAX_SPAN
+            ),
+        }
+    }
+}
+
+impl error::Error for AreaMapError {}
+
+/// Which of the input points is closest to a cell.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Closest {
+    /// The point with this index in the input is strictly closer than any other.
+    Point(usize),
+    /// Two or more points are equally close.
+    Tie,
+}
+
+/// The cells closest to one of the input points.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Area {
+    /// The number of cells inside the bounds of the points which are closest to this point.
+    pub size: usize,
+    /// Whether the area carries on forever outside the bounds of the points.
+    pub infinite: bool,
+}
+
+/// Labels every cell in (and one cell around) the bounding box of a set of points
+/// with the point closest to it.
+///
+/// The labels are found with a breadth-first search which starts from all of the
+/// points at once, so each cell is visited once regardless of the number of points.
+/// Outside the bounding box every point lies to the same side of a cell, so moving
+/// further out changes all of the distances equally. An area is therefore infinite
+/// exactly when it reaches the ring of cells just outside the bounds.
+#[derive(Debug, Clone)]
+pub struct AreaMap {
+    origin: Point,
+    width: usize,
+    height: usize,
+    cells: Vec<Closest>,
+    areas: Vec<Area>,
+}
+
+impl AreaMap {
+    /// Labels the cells around the points. Returns an error if the points
+    /// cover more than `MAX_SPAN` cells along either axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no points.
+    pub fn new(points: &[Point]) -> Result<AreaMap, AreaMapError> {
+        let ((left, top), (right, bottom)) = bounds(points);
+        let span_x = i64::from(right) - i64::from(left) + 1;
+        let span_y = i64::from(bottom) - i64::from(top) + 1;
+        if span_x > MAX_SPAN || span_y > MAX_SPAN {
+            return Err(AreaMapError::TooLarge {
+                width: span_x,
+                height: span_y,
+            });
+        }
+
+        let origin = (left - 1, top - 1);
+        let width = (right - left + 3) as usize;
+        let height = (bottom - top + 3) as usize;
+
+        let mut distances = vec![u32::MAX; width * height];
+        let mut cells = vec![Closest::Tie; width * height];
+        let mut queue = VecDeque::with_capacity(points.len());
+
+        for (number, &(x, y)) in points.iter().enumerate() {
+            let index = (y - origin.1) as usize * width + (x - origin.0) as usize;
+            if distances[index] == 0 {
+                // Two points share the same position.
+                cells[index] = Closest::Tie;
+            } else {
+                distances[index] = 0;
+                cells[index] = Closest::Point(number);
+                queue.push_back(index);
+            }
+        }
+
+        while let Some(index) = queue.pop_front() {
+            let distance = distances[index] + 1;
+            let closest = cells[index];
+            let (column, row) = (index % width, index / width);
+
+            let mut neighbours = [None; 4];
+            if column > 0 {
+                neighbours[0] = Some(index - 1);
+            }
+            if column + 1 < width {
+                neighbours[1] = Some(index + 1);
+            }
+            if row > 0 {
+                neighbours[2] = Some(index - width);
+            }
+            if row + 1 < height {
+                neighbours[3] = Some(index + width);
+            }
+
+            for neighbour in neighbours.iter().filter_map(|&n| n) {
+                if distances[neighbour] == u32::MAX {
+                    distances[neighbour] = distance;
+                    cells[neighbour] = closest;
+                    queue.push_back(neighbour);
+                } else if distances[neighbour] == distance && cells[neighbour] != closest {
+                    cells[neighbour] = Closest::Tie;
+                }
+            }
+        }
+
+        let mut areas = vec![Area::default(); points.len()];
+
+        for (index, cell) in cells.iter().enumerate() {
+            if let Closest::Point(number) = *cell {
+                let (column, row) = (index % width, index / width);
+                if column == 0 || row == 0 || column == width - 1 || row == height - 1 {
+                    areas[number].infinite = true;
+                } else {
+                    areas[number].size += 1;
+                }
+            }
+        }
+
+        Ok(AreaMap {
+            origin,
+            width,
+            height,
+            cells,
+            areas,
+        })
+    }
+
+    /// The area closest to each of the input points, in the same order as the points.
+    pub fn areas(&self) -> &[Area] {
+        &self.areas
+    }
+
+    /// The top left and bottom right corners of the labelled cells.
+    pub fn extent(&self) -> (Point, Point) {
+        let (left, top) = self.origin;
+        let right = left + self.width as i32 - 1;
+        let bottom = top + self.height as i32 - 1;
+
+        ((left, top), (right, bottom))
+    }
+
+    /// Finds the closest point to the given position, or `None` if the position is
+    /// more than one cell outside the bounds of the points.
+    pub fn closest(&self, (x, y): Point) -> Option<Closest> {
+        let column = x - self.origin.0;
+        let row = y - self.origin.1;
+
+        if column < 0 || row < 0 || column as usize >= self.width || row as usize >= self.height {
+            None
+        } else {
+            Some(self.cells[row as usize * self.width + column as usize])
+        }
+    }
+}
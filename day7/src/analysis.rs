@@ -0,0 +1,132 @@
+use crate::{parallel_construction, CycleError, DependencyGraph};
+use std::{cmp::Reverse, collections::HashMap, hash::Hash};
+
+/// The longest chain of steps which have to be done one after another.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CriticalPath<T> {
+    /// The total time taken by the steps in the chain. No number of workers can
+    /// finish all of the steps any faster than this.
+    pub length: usize,
+    pub steps: Vec<T>,
+}
+
+/// Finds the critical path through the steps. If several chains take equally long,
+/// the one with the earliest step names is chosen.
+pub fn critical_path<T: Ord + Hash + Clone>(
+    graph: &DependencyGraph<T>,
+    step_time: impl Fn(&T) -> usize,
+) -> Result<CriticalPath<T>, CycleError<T>> {
+    let order = graph.topological_order()?;
+
+    // The earliest time each step can be finished, and the prerequisite
+    // which holds it up the longest.
+    let mut finish_times: HashMap<&T, usize> = HashMap::with_capacity(order.len());
+    let mut held_up_by: HashMap<&T, &T> = HashMap::with_capacity(order.len());
+
+    for step in &order {
+        let latest_prerequisite = graph
+            .prerequisites(step)
+            .max_by_key(|&prerequisite| (finish_times[prerequisite], Reverse(prerequisite)));
+
+        let start = match latest_prerequisite {
+            Some(prerequisite) => {
+                held_up_by.insert(step, prerequisite);
+                finish_times[prerequisite]
+            }
+            None => 0,
+        };
+
+        finish_times.insert(step, start + step_time(step));
+    }
+
+    let last_step = order
+        .iter()
+        .max_by_key(|&step| (finish_times[step], Reverse(step)));
+
+    let mut steps = Vec::new();
+    let mut next = last_step;
+    while let Some(step) = next {
+        steps.push(step.clone());
+        next = held_up_by.get(step).cloned();
+    }
+    steps.reverse();
+
+    Ok(CriticalPath {
+        length: last_step.map_or(0, |step| finish_times[step]),
+        steps,
+    })
+}
+
+/// Finds how long it takes to carry out all of the steps with each number of
+/// workers from 1 to `max_workers`. The first element is the time for 1 worker.
+pub fn makespans<T: Ord + Hash + Clone>(
+    graph: &DependencyGraph<T>,
+    max_workers: usize,
+    step_time: impl Fn(&T) -> usize,
+) -> Result<Vec<usize>, CycleError<T>> {
+    (1..=max_workers)
+        .map(|workers| parallel_construction(graph, workers, &step_time))
+        .collect()
+}
+
+/// Finds the smallest number of workers that can finish all of the steps in the
+/// length of the critical path.
+///
+/// Adding workers does not always speed things up when steps are handed out greedily,
+/// so every number of workers is tried in turn. With one worker per step, every
+/// step can start as soon as it is ready, so the search always succeeds.
+pub fn minimum_workers<T: Ord + Hash + Clone>(
+    graph: &DependencyGraph<T>,
+    step_time: impl Fn(&T) -> usize,
+) -> Result<usize, CycleError<T>> {
+    let shortest = critical_path(graph, &step_time)?.length;
+
+    for workers in 1..graph.len() {
+        if parallel_construction(graph, workers, &step_time)? == shortest {
+            return Ok(workers);
+        }
+    }
+
+    Ok(graph.len().max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{example_input, test_step_time};
+
+    #[test]
+    fn example_critical_path() {
+        let path = critical_path(&example_input(), test_step_time).unwrap();
+
+        assert_eq!(14, path.length);
+        assert_eq!(vec!["C", "F", "E"], path.steps);
+    }
+
+    #[test]
+    fn example_makespans() {
+        assert_eq!(
+            vec![21, 15, 14, 14, 14, 14],
+            makespans(&example_input(), 6, test_step_time).unwrap()
+        );
+    }
+
+    #[test]
+    fn example_minimum_workers() {
+        assert_eq!(
+            3,
+            minimum_workers(&example_input(), test_step_time).unwrap()
+        );
+    }
+
+    #[test]
+    fn independent_steps_need_one_worker_each() {
+        let mut graph = DependencyGraph::new();
+        for step in 1..=4 {
+            graph.add_step(step);
+        }
+
+        assert_eq!(4, minimum_workers(&graph, |_| 10).unwrap());
+        assert_eq!(10, critical_path(&graph, |_| 10).unwrap().length);
+    }
+}
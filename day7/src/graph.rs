@@ -0,0 +1,217 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    error::Error,
+    fmt,
+    hash::Hash,
+};
+
+/// A set of steps, some of which cannot begin until others have finished.
+#[derive(Debug, Clone)]
+pub struct DependencyGraph<T> {
+    prerequisites: HashMap<T, HashSet<T>>,
+    successors: HashMap<T, HashSet<T>>,
+}
+
+impl<T: Ord + Hash + Clone> DependencyGraph<T> {
+    pub fn new() -> DependencyGraph<T> {
+        DependencyGraph {
+            prerequisites: HashMap::new(),
+            successors: HashMap::new(),
+        }
+    }
+
+    /// Adds a step that does not depend on anything (yet).
+    pub fn add_step(&mut self, step: T) {
+        self.successors.entry(step.clone()).or_default();
+        self.prerequisites.entry(step).or_default();
+    }
+
+    /// Records that `step` cannot begin until `prerequisite` has finished.
+    pub fn add_dependency(&mut self, prerequisite: T, step: T) {
+        self.add_step(prerequisite.clone());
+        self.add_step(step.clone());
+
+        self.prerequisites
+            .get_mut(&step)
+            .unwrap()
+            .insert(prerequisite.clone());
+        self.successors.get_mut(&prerequisite).unwrap().insert(step);
+    }
+
+    pub fn len(&self) -> usize {
+        self.prerequisites.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prerequisites.is_empty()
+    }
+
+    pub fn contains(&self, step: &T) -> bool {
+        self.prerequisites.contains_key(step)
+    }
+
+    /// All of the steps, in no particular order.
+    pub fn steps(&self) -> impl Iterator<Item = &T> {
+        self.prerequisites.keys()
+    }
+
+    /// The steps which must finish before `step` can begin.
+    pub fn prerequisites<'a>(&'a self, step: &T) -> impl Iterator<Item = &'a T> {
+        self.prerequisites.get(step).into_iter().flatten()
+    }
+
+    /// The steps which cannot begin until `step` has finished.
+    pub fn successors<'a>(&'a self, step: &T) -> impl Iterator<Item = &'a T> {
+        self.successors.get(step).into_iter().flatten()
+    }
+
+    /// Puts the steps into an order in which they can be performed one at a time.
+    /// Whenever several steps are available, the smallest is done first.
+    pub fn topological_order(&self) -> Result<Vec<T>, CycleError<T>> {
+        let mut waiting_for: HashMap<&T, usize> = self
+            .prerequisites
+            .iter()
+            .map(|(step, prerequisites)| (step, prerequisites.len()))
+            .collect();
+
+        let mut available: BinaryHeap<Reverse<&T>> = waiting_for
+            .iter()
+            .filter(|(_, &count)| count == 0)
+            .map(|(&step, _)| Reverse(step))
+            .collect();
+
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(Reverse(step)) = available.pop() {
+            order.push(step.clone());
+
+            for successor in self.successors(step) {
+                let count = waiting_for.get_mut(successor).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    available.push(Reverse(successor));
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            Ok(order)
+        } else {
+            let done: HashSet<&T> = order.iter().collect();
+            Err(self.find_cycle(&done))
+        }
+    }
+
+    /// Finds a cycle among the steps which have not been done.
+    ///
+    /// A step can only be left over if one of its prerequisites was also left over,
+    /// so following prerequisites backwards from any remaining step must eventually
+    /// come back to a step already visited.
+    fn find_cycle(&self, done: &HashSet<&T>) -> CycleError<T> {
+        let mut step = self
+            .steps()
+            .filter(|step| !done.contains(step))
+            .min()
+            .expect("No steps remaining");
+
+        let mut path: Vec<&T> = Vec::new();
+        let mut visited: HashMap<&T, usize> = HashMap::new();
+
+        while !visited.contains_key(step) {
+            visited.insert(step, path.len());
+            path.push(step);
+            step = self
+                .prerequisites(step)
+                .filter(|prerequisite| !done.contains(prerequisite))
+                .min()
+                .expect("Remaining step has no remaining prerequisites");
+        }
+
+        // The path was followed from each step to one of its prerequisites,
+        // so reverse it to list the steps in the order they would have to happen,
+        // starting from the smallest.
+        let mut cycle: Vec<T> = path[visited[step]..].iter().map(|&s| s.clone()).collect();
+        cycle.reverse();
+
+        let smallest = (0..cycle.len()).min_by_key(|&n| &cycle[n]).unwrap_or(0);
+        cycle.rotate_left(smallest);
+
+        CycleError { cycle }
+    }
+}
+
+impl<T: Ord + Hash + Clone> Default for DependencyGraph<T> {
+    fn default() -> DependencyGraph<T> {
+        DependencyGraph::new()
+    }
+}
+
+/// The steps cannot all be done, because some of them depend on each other.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CycleError<T> {
+    /// Each step in the cycle must finish before the next one can begin,
+    /// and the last one must finish before the first.
+    pub cycle: Vec<T>,
+}
+
+impl<T: fmt::Display> fmt::Display for CycleError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Steps depend on each other in a cycle: ")?;
+
+        for step in &self.cycle {
+            write!(f, "{} -> ", step)?;
+        }
+
+        match self.cycle.first() {
+            Some(first) => write!(f, "{}", first),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> Error for CycleError<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order_prefers_smallest_available_step() {
+        let mut graph = DependencyGraph::new();
+        graph.add_dependency(3, 1);
+        graph.add_dependency(2, 1);
+        graph.add_step(5);
+        graph.add_dependency(4, 2);
+
+        assert_eq!(Ok(vec![3, 4, 2, 1, 5]), graph.topological_order());
+    }
+
+    #[test]
+    fn cycle_is_reported() {
+        let mut graph = DependencyGraph::new();
+        graph.add_dependency('A', 'B');
+        graph.add_dependency('B', 'C');
+        graph.add_dependency('C', 'D');
+        graph.add_dependency('D', 'B');
+        graph.add_dependency('D', 'E');
+
+        let error = graph.topological_order().unwrap_err();
+        assert_eq!(vec!['B', 'C', 'D'], error.cycle);
+        assert_eq!(
+            "Steps depend on each other in a cycle: B -> C -> D -> B",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn self_dependency_is_a_cycle() {
+        let mut graph = DependencyGraph::new();
+        graph.add_dependency("X", "X");
+
+        assert_eq!(
+            Err(CycleError { cycle: vec!["X"] }),
+            graph.topological_order()
+        );
+    }
+}
@@ -0,0 +1,100 @@
+mod analysis;
+mod graph;
+mod parsing;
+mod policy;
+mod schedule;
+pub use crate::analysis::{critical_path, makespans, minimum_workers, CriticalPath};
+pub use crate::graph::{CycleError, DependencyGraph};
+pub use crate::parsing::{parse_instructions, ParseError};
+pub use crate::policy::{
+    Alphabetical, CriticalPathFirst, LongestProcessingTimeFirst, MostSuccessorsFirst,
+    SchedulingPolicy,
+};
+pub use crate::schedule::{simulate, simulate_with_policy, Assignment, Schedule};
+use std::hash::Hash;
+
+/// Finds the order in which the steps must be performed by a single worker,
+/// written out with `format_steps`.
+pub fn find_order(graph: &DependencyGraph<String>) -> Result<String, CycleError<String>> {
+    Ok(format_steps(&graph.topological_order()?))
+}
+
+/// Writes out a list of steps. Single-letter steps are written one after another,
+/// as in the puzzle, but if any step has a longer name they are separated by
+/// commas so that the names can be told apart.
+pub fn format_steps(steps: &[String]) -> String {
+    if steps.iter().all(|step| step.chars().count() <= 1) {
+        steps.concat()
+    } else {
+        steps.join(", ")
+    }
+}
+
+/// Finds how long it takes a team of workers to carry out all of the steps.
+/// Panics if there are no workers.
+pub fn parallel_construction<T: Ord + Hash + Clone>(
+    graph: &DependencyGraph<T>,
+    num_workers: usize,
+    step_time: impl Fn(&T) -> usize,
+) -> Result<usize, CycleError<T>> {
+    Ok(simulate(graph, num_workers, step_time)?.makespan())
+}
+
+/// Each step takes 60 seconds plus a number of seconds corresponding to its letter
+/// (A = 1, B = 2 and so on). A step with a longer name takes 60 seconds plus the
+/// number for each of its letters.
+pub fn time_for_step(step: &str) -> usize {
+    60 + step
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|letter| (letter.to_ascii_uppercase() as u8 - b'A' + 1) as usize)
+        .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The graph from the puzzle's example, shared by the tests of every module.
+    pub(crate) fn example_input() -> DependencyGraph<String> {
+        parse_instructions(include_str!("example_input.txt")).unwrap()
+    }
+
+    /// The step times from the puzzle's example, without the extra 60 seconds.
+    pub(crate) fn test_step_time<S: AsRef<str>>(step: &S) -> usize {
+        time_for_step(step.as_ref()) - 60
+    }
+
+    #[test]
+    fn test_find_order() {
+        let graph = example_input();
+        let sequence = find_order(&graph).unwrap();
+
+        assert_eq!("CABDFE".to_string(), sequence);
+    }
+
+    #[test]
+    fn longer_step_names_are_separated() {
+        let graph = parse_instructions(
+            "Step Foundations must be finished before step Walls can begin.\n\
+             Step Walls must be finished before step Roof can begin.",
+        )
+        .unwrap();
+
+        assert_eq!("Foundations, Walls, Roof", find_order(&graph).unwrap());
+    }
+
+    #[test]
+    fn test_part2() {
+        let graph = example_input();
+        let time = parallel_construction(&graph, 2, test_step_time).unwrap();
+        assert_eq!(15, time);
+    }
+
+    #[test]
+    fn test_time_for_step() {
+        assert_eq!(61, time_for_step("A"));
+        assert_eq!(86, time_for_step("Z"));
+        assert_eq!(63, time_for_step("AB"));
+    }
+}
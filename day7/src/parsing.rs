@@ -0,0 +1,110 @@
+use crate::DependencyGraph;
+use std::{error, fmt};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseError {
+    IncorrectFormat { line: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::IncorrectFormat { line } => write!(
+                f,
+                "Line {}: expected \"Step X must be finished before step Y can begin.\"",
+                line
+            ),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Reads instructions of the form "Step X must be finished before step Y can begin.",
+/// one per line. Step names can be any word, and the other words are not case
+/// sensitive. Blank lines are ignored, and line numbers in errors count from 1.
+pub fn parse_instructions(input: &str) -> Result<DependencyGraph<String>, ParseError> {
+    let mut graph = DependencyGraph::new();
+
+    for (index, line) in input.lines().enumerate() {
+        if !line.trim().is_empty() {
+            let (prerequisite, step) =
+                parse_instruction(line).ok_or(ParseError::IncorrectFormat { line: index + 1 })?;
+            graph.add_dependency(prerequisite.to_string(), step.to_string());
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Splits an instruction into the prerequisite and the step which depends on it.
+fn parse_instruction(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    let line = line.strip_suffix('.').unwrap_or(line);
+    let words: Vec<&str> = line.split_whitespace().collect();
+
+    match words.as_slice() {
+        [step1, prerequisite, must, be, finished, before, step2, step, can, begin]
+            if step1.eq_ignore_ascii_case("step")
+                && must.eq_ignore_ascii_case("must")
+                && be.eq_ignore_ascii_case("be")
+                && finished.eq_ignore_ascii_case("finished")
+                && before.eq_ignore_ascii_case("before")
+                && step2.eq_ignore_ascii_case("step")
+                && can.eq_ignore_ascii_case("can")
+                && begin.eq_ignore_ascii_case("begin") =>
+        {
+            Some((prerequisite, step))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instruction() {
+        assert_eq!(
+            Some(("C", "A")),
+            parse_instruction("Step C must be finished before step A can begin.")
+        );
+        assert_eq!(
+            Some(("Paint", "Dry")),
+            parse_instruction("  step Paint must be finished before step Dry can begin ")
+        );
+        assert_eq!(
+            None,
+            parse_instruction("Step C must be done before step A can begin.")
+        );
+        assert_eq!(
+            None,
+            parse_instruction("Step C must be finished before step can begin.")
+        );
+    }
+
+    #[test]
+    fn parse_errors_report_line_numbers() {
+        let input = "Step C must be finished before step A can begin.\n\nStep C, then A\n";
+        assert_eq!(
+            ParseError::IncorrectFormat { line: 3 },
+            parse_instructions(input).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn multi_character_step_names() {
+        let graph = parse_instructions(
+            "Step Foundations must be finished before step Walls can begin.\n\
+             Step Walls must be finished before step Roof can begin.",
+        )
+        .unwrap();
+
+        assert_eq!(3, graph.len());
+        assert_eq!(
+            vec!["Foundations", "Walls", "Roof"],
+            graph.topological_order().unwrap()
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{CycleError, DependencyGraph};
+use std::{collections::HashMap, hash::Hash};
+
+/// Decides which of the available steps an idle worker should start next.
+///
+/// Of the steps that are available, the one with the highest priority is started
+/// first. Steps with the same priority are started in order of their names.
+pub trait SchedulingPolicy<T> {
+    fn priority(&self, step: &T) -> usize;
+}
+
+/// Starts the available steps in order of their names, as in the puzzle.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Alphabetical;
+
+impl<T> SchedulingPolicy<T> for Alphabetical {
+    fn priority(&self, _step: &T) -> usize {
+        0
+    }
+}
+
+/// Starts the step which takes the longest.
+#[derive(Debug, Copy, Clone)]
+pub struct LongestProcessingTimeFirst<F> {
+    step_time: F,
+}
+
+impl<F> LongestProcessingTimeFirst<F> {
+    pub fn new(step_time: F) -> LongestProcessingTimeFirst<F> {
+        LongestProcessingTimeFirst { step_time }
+    }
+}
+
+impl<T, F: Fn(&T) -> usize> SchedulingPolicy<T> for LongestProcessingTimeFirst<F> {
+    fn priority(&self, step: &T) -> usize {
+        (self.step_time)(step)
+    }
+}
+
+/// Starts the step which the most other steps are directly waiting for.
+#[derive(Debug, Copy, Clone)]
+pub struct MostSuccessorsFirst<'a, T> {
+    graph: &'a DependencyGraph<T>,
+}
+
+impl<'a, T> MostSuccessorsFirst<'a, T> {
+    pub fn new(graph: &'a DependencyGraph<T>) -> MostSuccessorsFirst<'a, T> {
+        MostSuccessorsFirst { graph }
+    }
+}
+
+impl<'a, T: Ord + Hash + Clone> SchedulingPolicy<T> for MostSuccessorsFirst<'a, T> {
+    fn priority(&self, step: &T) -> usize {
+        self.graph.successors(step).count()
+    }
+}
+
+/// Starts the step with the longest chain of work still to come after it
+/// (including the step itself).
+#[derive(Debug, Clone)]
+pub struct CriticalPathFirst<T> {
+    remaining_time: HashMap<T, usize>,
+}
+
+impl<T: Ord + Hash + Clone> CriticalPathFirst<T> {
+    pub fn new(
+        graph: &DependencyGraph<T>,
+        step_time: impl Fn(&T) -> usize,
+    ) -> Result<CriticalPathFirst<T>, CycleError<T>> {
+        let mut remaining_time: HashMap<T, usize> = HashMap::with_capacity(graph.len());
+
+        // Work backwards, so that every successor is dealt with before its prerequisites.
+        for step in graph.topological_order()?.into_iter().rev() {
+            let after = graph
+                .successors(&step)
+                .map(|successor| remaining_time[successor])
+                .max()
+                .unwrap_or(0);
+            let time = step_time(&step) + after;
+            remaining_time.insert(step, time);
+        }
+
+        Ok(CriticalPathFirst { remaining_time })
+    }
+}
+
+impl<T: Ord + Hash> SchedulingPolicy<T> for CriticalPathFirst<T> {
+    fn priority(&self, step: &T) -> usize {
+        self.remaining_time.get(step).cloned().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{example_input, test_step_time};
+    use crate::{parse_instructions, simulate_with_policy};
+
+    fn first_steps(policy: &impl SchedulingPolicy<String>) -> Vec<String> {
+        let graph = example_input();
+        let schedule = simulate_with_policy(&graph, 2, test_step_time, policy).unwrap();
+        schedule
+            .assignments
+            .iter()
+            .take(4)
+            .map(|a| a.step.clone())
+            .collect()
+    }
+
+    fn one_worker_order(
+        graph: &DependencyGraph<String>,
+        policy: &impl SchedulingPolicy<String>,
+    ) -> Vec<String> {
+        simulate_with_policy(graph, 1, test_step_time, policy)
+            .unwrap()
+            .assignments
+            .into_iter()
+            .map(|a| a.step)
+            .collect()
+    }
+
+    #[test]
+    fn test_critical_path_priorities() {
+        let policy = CriticalPathFirst::new(&example_input(), test_step_time).unwrap();
+
+        assert_eq!(14, policy.priority(&"C".to_string()));
+        assert_eq!(10, policy.priority(&"A".to_string()));
+        assert_eq!(11, policy.priority(&"F".to_string()));
+        assert_eq!(5, policy.priority(&"E".to_string()));
+    }
+
+    #[test]
+    fn policies_change_the_order_of_steps() {
+        let graph = example_input();
+
+        assert_eq!(vec!["C", "A", "F", "B"], first_steps(&Alphabetical));
+        assert_eq!(
+            vec!["C", "F", "A", "D"],
+            first_steps(&LongestProcessingTimeFirst::new(test_step_time))
+        );
+        // In the example, A has the most successors and the other steps tie, so
+        // this happens to match the alphabetical order.
+        assert_eq!(
+            vec!["C", "A", "F", "B"],
+            first_steps(&MostSuccessorsFirst::new(&graph))
+        );
+        assert_eq!(
+            vec!["C", "F", "A", "D"],
+            first_steps(&CriticalPathFirst::new(&graph, test_step_time).unwrap())
+        );
+    }
+
+    #[test]
+    fn most_successors_first_starts_the_busiest_step() {
+        // A and B can both start straight away, but three steps wait for B.
+        let graph = parse_instructions(
+            "Step A must be finished before step X can begin.\n\
+             Step B must be finished before step X can begin.\n\
+             Step B must be finished before step Y can begin.\n\
+             Step B must be finished before step Z can begin.",
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec!["A", "B", "X", "Y", "Z"],
+            one_worker_order(&graph, &Alphabetical)
+        );
+        assert_eq!(
+            vec!["B", "A", "X", "Y", "Z"],
+            one_worker_order(&graph, &MostSuccessorsFirst::new(&graph))
+        );
+    }
+}
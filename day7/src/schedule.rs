@@ -0,0 +1,312 @@
+use crate::{Alphabetical, CycleError, DependencyGraph, SchedulingPolicy};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fmt::{self, Write},
+    hash::Hash,
+};
+
+/// One step carried out by one worker.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Assignment<T> {
+    /// The index of the worker, counting from 0.
+    pub worker: usize,
+    pub step: T,
+    /// The second at which the worker starts the step.
+    pub start: usize,
+    /// The second at which the step is finished, and the worker is free again.
+    pub end: usize,
+}
+
+/// Which worker did each step, and when.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Schedule<T> {
+    pub workers: usize,
+    /// The assignments, in the order in which they were started.
+    pub assignments: Vec<Assignment<T>>,
+}
+
+impl<T> Schedule<T> {
+    /// The time taken to complete all of the steps.
+    pub fn makespan(&self) -> usize {
+        self.assignments.iter().map(|a| a.end).max().unwrap_or(0)
+    }
+}
+
+impl<T: fmt::Display> Schedule<T> {
+    /// Draws a table showing what each worker is doing every second, in the same
+    /// format as the example in the puzzle.
+    pub fn gantt(&self) -> String {
+        let headings: Vec<String> = (1..=self.workers)
+            .map(|n| format!("Worker {}", n))
+            .collect();
+        let names: Vec<String> = self
+            .assignments
+            .iter()
+            .map(|a| a.step.to_string())
+            .collect();
+        let widths: Vec<usize> = headings
+            .iter()
+            .map(|heading| {
+                names
+                    .iter()
+                    .map(String::len)
+                    .fold(heading.len(), usize::max)
+            })
+            .collect();
+
+        let mut finished: Vec<usize> = (0..self.assignments.len()).collect();
+        finished.sort_by_key(|&n| (self.assignments[n].end, names[n].clone()));
+
+        let mut chart = String::from("Second");
+        for (heading, &width) in headings.iter().zip(&widths) {
+            write!(chart, "   {:^width$}", heading, width = width).unwrap();
+        }
+        chart.push_str("   Done\n");
+
+        let mut done = String::new();
+        let mut finished = finished.into_iter().peekable();
+
+        for second in 0..=self.makespan() {
+            while let Some(&n) = finished.peek() {
+                if self.assignments[n].end > second {
+                    break;
+                }
+
+                done.push_str(&names[n]);
+                finished.next();
+            }
+
+            let mut row = format!("{:>4}  ", second);
+            for (worker, &width) in widths.iter().enumerate() {
+                let cell = self
+                    .assignments
+                    .iter()
+                    .position(|a| a.worker == worker && a.start <= second && second < a.end)
+                    .map_or(".", |n| &names[n]);
+                write!(row, "   {:^width$}", cell, width = width).unwrap();
+            }
+            write!(row, "   {}", done).unwrap();
+
+            chart.push_str(row.trim_end());
+            chart.push('\n');
+        }
+
+        chart
+    }
+
+    /// Writes the schedule as a JSON object.
+    pub fn to_json(&self) -> String {
+        let assignments: Vec<String> = self
+            .assignments
+            .iter()
+            .map(|a| {
+                format!(
+                    "{{\"worker\":{},\"step\":{},\"start\":{},\"end\":{}}}",
+                    a.worker,
+                    json_string(&a.step.to_string()),
+                    a.start,
+                    a.end
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"workers\":{},\"makespan\":{},\"assignments\":[{}]}}",
+            self.workers,
+            self.makespan(),
+            assignments.join(",")
+        )
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if c.is_control() => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// Works out how a team of workers would carry out the steps.
+///
+/// Whenever a worker is idle and a step is available, the lowest-numbered idle worker
+/// takes the first available step. Rather than counting off every second, the
+/// simulation jumps straight to the next time at which a worker finishes.
+///
+/// # Panics
+///
+/// Panics if there are no workers.
+pub fn simulate<T: Ord + Hash + Clone>(
+    graph: &DependencyGraph<T>,
+    num_workers: usize,
+    step_time: impl Fn(&T) -> usize,
+) -> Result<Schedule<T>, CycleError<T>> {
+    simulate_with_policy(graph, num_workers, step_time, &Alphabetical)
+}
+
+/// Works out how a team of workers would carry out the steps, with the
+/// `policy` deciding which available step to start next.
+///
+/// # Panics
+///
+/// Panics if there are no workers.
+pub fn simulate_with_policy<T: Ord + Hash + Clone>(
+    graph: &DependencyGraph<T>,
+    num_workers: usize,
+    step_time: impl Fn(&T) -> usize,
+    policy: &impl SchedulingPolicy<T>,
+) -> Result<Schedule<T>, CycleError<T>> {
+    assert!(num_workers > 0, "The steps need at least one worker");
+
+    // Make sure that every step can be reached before trying to schedule them.
+    graph.topological_order()?;
+
+    let mut waiting_for: HashMap<&T, usize> = graph
+        .steps()
+        .map(|step| (step, graph.prerequisites(step).count()))
+        .collect();
+    let mut available: BinaryHeap<(usize, Reverse<&T>)> = waiting_for
+        .iter()
+        .filter(|(_, &count)| count == 0)
+        .map(|(&step, _)| (policy.priority(step), Reverse(step)))
+        .collect();
+
+    let mut idle_workers: BinaryHeap<Reverse<usize>> = (0..num_workers).map(Reverse).collect();
+    let mut in_progress: BinaryHeap<Reverse<(usize, usize, &T)>> = BinaryHeap::new();
+    let mut assignments = Vec::with_capacity(graph.len());
+    let mut time = 0;
+
+    loop {
+        while !available.is_empty() && !idle_workers.is_empty() {
+            let (_, Reverse(step)) = available.pop().unwrap();
+            let Reverse(worker) = idle_workers.pop().unwrap();
+            let end = time + step_time(step);
+
+            in_progress.push(Reverse((end, worker, step)));
+            assignments.push(Assignment {
+                worker,
+                step: step.clone(),
+                start: time,
+                end,
+            });
+        }
+
+        // Move on to the next time that a step is finished, and finish every step
+        // that ends at that time before handing out any more work.
+        match in_progress.peek() {
+            Some(&Reverse((end, _, _))) => time = end,
+            None => break,
+        }
+
+        while let Some(&Reverse((end, worker, step))) = in_progress.peek() {
+            if end > time {
+                break;
+            }
+
+            in_progress.pop();
+            idle_workers.push(Reverse(worker));
+
+            for successor in graph.successors(step) {
+                let count = waiting_for.get_mut(successor).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    available.push((policy.priority(successor), Reverse(successor)));
+                }
+            }
+        }
+    }
+
+    Ok(Schedule {
+        workers: num_workers,
+        assignments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{example_input, test_step_time};
+
+    fn example_schedule() -> Schedule<String> {
+        simulate(&example_input(), 2, test_step_time).unwrap()
+    }
+
+    #[test]
+    fn example_assignments() {
+        let schedule = example_schedule();
+        let summary: Vec<(usize, &str, usize, usize)> = schedule
+            .assignments
+            .iter()
+            .map(|a| (a.worker, a.step.as_str(), a.start, a.end))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (0, "C", 0, 3),
+                (0, "A", 3, 4),
+                (1, "F", 3, 9),
+                (0, "B", 4, 6),
+                (0, "D", 6, 10),
+                (0, "E", 10, 15),
+            ],
+            summary
+        );
+        assert_eq!(15, schedule.makespan());
+    }
+
+    #[test]
+    fn example_gantt_chart() {
+        let expected = "\
+Second   Worker 1   Worker 2   Done
+   0        C          .
+   1        C          .
+   2        C          .
+   3        A          F       C
+   4        B          F       CA
+   5        B          F       CA
+   6        D          F       CAB
+   7        D          F       CAB
+   8        D          F       CAB
+   9        D          .       CABF
+  10        E          .       CABFD
+  11        E          .       CABFD
+  12        E          .       CABFD
+  13        E          .       CABFD
+  14        E          .       CABFD
+  15        .          .       CABFDE
+";
+
+        assert_eq!(expected, example_schedule().gantt());
+    }
+
+    #[test]
+    fn example_json() {
+        let mut schedule = example_schedule();
+        schedule.assignments.truncate(2);
+        schedule.assignments[1].step = "say \"A\"".to_string();
+
+        assert_eq!(
+            "{\"workers\":2,\"makespan\":4,\"assignments\":[\
+             {\"worker\":0,\"step\":\"C\",\"start\":0,\"end\":3},\
+             {\"worker\":0,\"step\":\"say \\\"A\\\"\",\"start\":3,\"end\":4}]}",
+            schedule.to_json()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one worker")]
+    fn no_workers() {
+        let _ = simulate(&example_input(), 0, |_| 1);
+    }
+}
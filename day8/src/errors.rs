@@ -1,24 +1,56 @@
-use std::{error::Error, fmt, num::ParseIntError};
+use std::{error::Error, fmt, io, num::ParseIntError};
 
-#[derive(Debug, Clone, Copy)]
+/// An error in the list of numbers describing a tree. Offsets count the
+/// numbers from the start of the list, starting at 0.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum TreeParseError {
     InvalidNumber,
-    MissingData,
+    Io(io::ErrorKind),
+    MissingData { offset: usize },
+    NegativeCount { offset: usize },
+    Overflow { offset: usize },
+    TrailingData { offset: usize },
 }
 
 impl fmt::Display for TreeParseError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             TreeParseError::InvalidNumber => write!(f, "Cannot parse number"),
-            TreeParseError::MissingData => write!(f, "Unexpected end of data"),
+            TreeParseError::Io(kind) => write!(f, "Cannot read input: {}", io::Error::from(*kind)),
+            TreeParseError::MissingData { offset } => {
+                write!(f, "Unexpected end of data after {} numbers", offset)
+            }
+            TreeParseError::NegativeCount { offset } => {
+                write!(f, "Negative child or metadata count at offset {}", offset)
+            }
+            TreeParseError::Overflow { offset } => {
+                write!(
+                    f,
+                    "The sum of the metadata is too large at offset {}",
+                    offset
+                )
+            }
+            TreeParseError::TrailingData { offset } => {
+                write!(
+                    f,
+                    "Unexpected data after the end of the tree at offset {}",
+                    offset
+                )
+            }
         }
     }
 }
 
 impl Error for TreeParseError {}
 
+impl From<io::Error> for TreeParseError {
+    fn from(error: io::Error) -> TreeParseError {
+        TreeParseError::Io(error.kind())
+    }
+}
+
 impl From<ParseIntError> for TreeParseError {
     fn from(_: ParseIntError) -> TreeParseError {
         TreeParseError::InvalidNumber
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,10 @@
 pub mod errors;
+pub mod stream;
+pub mod traversal;
 use crate::errors::TreeParseError;
+use crate::stream::Numbers;
+use crate::traversal::{BreadthFirst, DepthFirst};
+use std::{convert::TryFrom, fmt, io};
 
 #[derive(Debug)]
 pub struct Node {
@@ -8,73 +13,284 @@ pub struct Node {
 }
 
 impl Node {
-    fn new(num_children: usize, num_metadata: usize) -> Node {
+    fn new() -> Node {
         Node {
-            children: Vec::with_capacity(num_children),
-            metadata: Vec::with_capacity(num_metadata),
+            children: Vec::new(),
+            metadata: Vec::new(),
         }
     }
 
-    pub fn sum_metadata(&self) -> i32 {
-        self.metadata.iter().sum::<i32>()
-            + self
-                .children
-                .iter()
-                .map(|child| child.sum_metadata())
-                .sum::<i32>()
+    pub fn children(&self) -> &[Node] {
+        &self.children
     }
 
-    pub fn value(&self) -> i32 {
-        if self.children.is_empty() {
-            self.metadata.iter().sum::<i32>()
-        } else {
-            let mut value = 0;
-            for n in &self.metadata {
-                let index = (n - 1) as usize;
-                if let Some(child) = self.children.get(index) {
-                    value += child.value();
-                }
+    pub fn metadata(&self) -> &[i32] {
+        &self.metadata
+    }
+
+    /// Iterates over this node and all of its descendants, visiting each node
+    /// before its children.
+    pub fn depth_first(&self) -> DepthFirst<'_> {
+        DepthFirst::new(self)
+    }
+
+    /// Iterates over this node and all of its descendants one level at a time.
+    pub fn breadth_first(&self) -> BreadthFirst<'_> {
+        BreadthFirst::new(self)
+    }
+
+    /// Finds the sum of the metadata of this node and all of its descendants.
+    /// Returns `None` if the sum is too large to fit in an `i64`.
+    pub fn sum_metadata(&self) -> Option<i64> {
+        let mut total: i64 = 0;
+        let mut stack = vec![self];
+
+        while let Some(node) = stack.pop() {
+            for &entry in &node.metadata {
+                total = total.checked_add(i64::from(entry))?;
+            }
+            stack.extend(&node.children);
+        }
+
+        Some(total)
+    }
+
+    /// Finds the value of the node: the sum of its metadata if it has no children,
+    /// or else the sum of the values of the children referred to by its metadata.
+    /// Returns `None` if the value is too large to fit in an `i64`.
+    pub fn value(&self) -> Option<i64> {
+        self.values()[0]
+    }
+
+    /// Finds the value of every node in the tree, in depth-first order.
+    ///
+    /// The nodes are visited in reverse, so that every node comes after all of its
+    /// descendants, and each value is worked out once from the values of the
+    /// children. Metadata entries which refer to the same child many times then
+    /// only cost one addition each.
+    fn values(&self) -> Vec<Option<i64>> {
+        let nodes: Vec<&Node> = self.depth_first().collect();
+        let mut values = vec![None; nodes.len()];
+        let mut subtree_sizes = vec![1; nodes.len()];
+
+        for (index, node) in nodes.iter().enumerate().rev() {
+            // In depth-first order, the first child comes straight after its parent,
+            // and each of the others comes straight after the previous child's subtree.
+            let mut child_indices = Vec::with_capacity(node.children.len());
+            let mut next_child = index + 1;
+            for _ in &node.children {
+                child_indices.push(next_child);
+                next_child += subtree_sizes[next_child];
             }
+            subtree_sizes[index] = next_child - index;
 
-            value
+            values[index] = if node.children.is_empty() {
+                node.metadata
+                    .iter()
+                    .try_fold(0i64, |total, &n| total.checked_add(i64::from(n)))
+            } else {
+                node.metadata.iter().try_fold(0i64, |total, &n| {
+                    let child = usize::try_from(n).ok().and_then(|n| n.checked_sub(1));
+                    match child.and_then(|child| child_indices.get(child)) {
+                        Some(&child_index) => total.checked_add(values[child_index]?),
+                        None => Some(total),
+                    }
+                })
+            };
         }
+
+        values
     }
 
+    /// Reads a tree from a list of numbers. Each node is given by the number of
+    /// children, the number of metadata entries, the children and then the metadata.
+    ///
+    /// The whole list must be used up by the tree. Nodes are read with an explicit
+    /// stack rather than by recursion, so very deep trees can be read.
     pub fn read<'a, T: Iterator<Item = &'a i32>>(numbers: &mut T) -> Result<Node, TreeParseError> {
-        let num_children = match numbers.next() {
-            Some(n) => *n as usize,
-            None => {
-                return Err(TreeParseError::MissingData);
+        Node::read_tree(Reader::new(numbers.map(|&n| Ok(n))))
+    }
+
+    /// Reads a tree from whitespace-separated numbers, parsing them as they are
+    /// read rather than collecting the whole input first.
+    pub fn read_from<R: io::Read>(input: R) -> Result<Node, TreeParseError> {
+        Node::read_tree(Reader::new(Numbers::new(input)))
+    }
+
+    fn read_tree<T>(mut reader: Reader<T>) -> Result<Node, TreeParseError>
+    where
+        T: Iterator<Item = Result<i32, TreeParseError>>,
+    {
+        let mut stack = vec![reader.read_header()?];
+
+        loop {
+            let top = stack.last_mut().unwrap();
+
+            if top.children_remaining > 0 {
+                top.children_remaining -= 1;
+                let child = reader.read_header()?;
+                stack.push(child);
+                continue;
             }
-        };
 
-        let num_metadata = match numbers.next() {
-            Some(n) => *n as usize,
-            None => {
-                return Err(TreeParseError::MissingData);
+            for _ in 0..top.metadata_count {
+                top.node.metadata.push(reader.read_number()?);
             }
-        };
 
-        let mut node = Node::new(num_children, num_metadata);
+            let finished = stack.pop().unwrap().node;
+            match stack.last_mut() {
+                Some(parent) => parent.node.children.push(finished),
+                None => {
+                    reader.finish()?;
+                    return Ok(finished);
+                }
+            }
+        }
+    }
 
-        for _ in 0..num_children {
-            let child = Node::read(numbers)?;
-            node.children.push(child);
+    /// Writes the tree back out in the format read by `Node::read`.
+    pub fn to_numbers(&self) -> Vec<i32> {
+        enum Visit<'a> {
+            Start(&'a Node),
+            Finish(&'a Node),
         }
 
-        for _ in 0..num_metadata {
-            match numbers.next() {
-                Some(n) => {
-                    node.metadata.push(*n);
+        let mut numbers = Vec::new();
+        let mut stack = vec![Visit::Start(self)];
+
+        while let Some(visit) = stack.pop() {
+            match visit {
+                Visit::Start(node) => {
+                    numbers.push(node.children.len() as i32);
+                    numbers.push(node.metadata.len() as i32);
+                    stack.push(Visit::Finish(node));
+                    stack.extend(node.children.iter().rev().map(Visit::Start));
                 }
+                Visit::Finish(node) => numbers.extend(&node.metadata),
+            }
+        }
 
-                None => {
-                    return Err(TreeParseError::MissingData);
+        numbers
+    }
+
+    /// Writes the tree as space-separated numbers in the format read by `Node::read`.
+    pub fn write_numbers<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        for (n, number) in self.to_numbers().iter().enumerate() {
+            if n > 0 {
+                write!(writer, " ")?;
+            }
+            write!(writer, "{}", number)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Draws the tree with one node per line, indenting each node below its parent.
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut values = self.values().into_iter();
+        let mut stack = vec![(0, self)];
+
+        while let Some((depth, node)) = stack.pop() {
+            // The nodes are drawn in depth-first order, which is the order of the values.
+            match values.next().unwrap() {
+                Some(value) => write!(f, "{:width$}Node (value {})", "", value, width = 2 * depth)?,
+                None => write!(f, "{:width$}Node (value overflows)", "", width = 2 * depth)?,
+            }
+
+            if !node.metadata.is_empty() {
+                write!(f, " metadata:")?;
+                for entry in &node.metadata {
+                    write!(f, " {}", entry)?;
                 }
-            };
+            }
+
+            writeln!(f)?;
+
+            stack.extend(node.children.iter().rev().map(|child| (depth + 1, child)));
         }
 
-        Ok(node)
+        Ok(())
+    }
+}
+
+impl Drop for Node {
+    /// Takes the descendants apart one at a time, so that dropping a very deep
+    /// tree does not overflow the stack.
+    fn drop(&mut self) {
+        let mut stack = std::mem::take(&mut self.children);
+
+        while let Some(mut node) = stack.pop() {
+            stack.append(&mut node.children);
+        }
+    }
+}
+
+/// A node which has been started but not finished while reading a tree.
+struct PartialNode {
+    node: Node,
+    children_remaining: usize,
+    metadata_count: usize,
+}
+
+struct Reader<T> {
+    numbers: T,
+    offset: usize,
+}
+
+impl<T: Iterator<Item = Result<i32, TreeParseError>>> Reader<T> {
+    fn new(numbers: T) -> Reader<T> {
+        Reader { numbers, offset: 0 }
+    }
+
+    fn read_number(&mut self) -> Result<i32, TreeParseError> {
+        let number = self.numbers.next().ok_or(TreeParseError::MissingData {
+            offset: self.offset,
+        })??;
+        self.offset += 1;
+
+        Ok(number)
+    }
+
+    fn read_count(&mut self) -> Result<usize, TreeParseError> {
+        let offset = self.offset;
+        let count = self.read_number()?;
+
+        if count < 0 {
+            Err(TreeParseError::NegativeCount { offset })
+        } else {
+            Ok(count as usize)
+        }
+    }
+
+    /// Reads the number of children and the number of metadata entries of a node.
+    fn read_counts(&mut self) -> Result<(usize, usize), TreeParseError> {
+        let children = self.read_count()?;
+        let metadata = self.read_count()?;
+
+        Ok((children, metadata))
+    }
+
+    fn read_header(&mut self) -> Result<PartialNode, TreeParseError> {
+        let (children_remaining, metadata_count) = self.read_counts()?;
+
+        Ok(PartialNode {
+            node: Node::new(),
+            children_remaining,
+            metadata_count,
+        })
+    }
+
+    /// Checks that the tree used up all of the numbers.
+    fn finish(mut self) -> Result<(), TreeParseError> {
+        match self.numbers.next() {
+            Some(Ok(_)) => Err(TreeParseError::TrailingData {
+                offset: self.offset,
+            }),
+            Some(Err(error)) => Err(error),
+            None => Ok(()),
+        }
     }
 }
 
@@ -91,12 +307,140 @@ mod tests {
     fn example_sum_of_metadata() {
         let root_node = example_tree();
 
-        assert_eq!(138, root_node.sum_metadata());
+        assert_eq!(Some(138), root_node.sum_metadata());
     }
 
     #[test]
     fn example_node_value() {
         let root_node = example_tree();
-        assert_eq!(66, root_node.value());
+        assert_eq!(Some(66), root_node.value());
+    }
+
+    fn read(numbers: &[i32]) -> Result<Node, TreeParseError> {
+        Node::read(&mut numbers.iter())
+    }
+
+    #[test]
+    fn trailing_data_is_rejected() {
+        assert_eq!(
+            TreeParseError::TrailingData { offset: 3 },
+            read(&[0, 1, 5, 7]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn negative_counts_are_rejected() {
+        assert_eq!(
+            TreeParseError::NegativeCount { offset: 3 },
+            read(&[1, 1, 0, -1, 5]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn missing_data_reports_offset() {
+        assert_eq!(
+            TreeParseError::MissingData { offset: 0 },
+            read(&[]).unwrap_err()
+        );
+        assert_eq!(
+            TreeParseError::MissingData { offset: 6 },
+            read(&[1, 2, 0, 1, 99, 1]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn very_deep_tree() {
+        let depth = 200_000;
+        let mut input = Vec::with_capacity(2 * depth + 3);
+        for _ in 0..depth {
+            input.push(1);
+            input.push(1);
+        }
+        input.extend(&[0, 1, 5]);
+        input.extend(std::iter::repeat_n(2, depth));
+
+        let root = read(&input).unwrap();
+        assert_eq!(Some(5 + 2 * depth as i64), root.sum_metadata());
+    }
+
+    #[test]
+    fn example_accessors() {
+        let root_node = example_tree();
+
+        assert_eq!(2, root_node.children().len());
+        assert_eq!(&[1, 1, 2], root_node.metadata());
+        assert_eq!(&[99], root_node.children()[1].children()[0].metadata());
+    }
+
+    #[test]
+    fn example_traversal_orders() {
+        let root_node = example_tree();
+
+        let depth_first: Vec<&[i32]> = root_node.depth_first().map(Node::metadata).collect();
+        assert_eq!(
+            vec![&[1, 1, 2][..], &[10, 11, 12], &[2], &[99]],
+            depth_first
+        );
+
+        let breadth_first: Vec<i32> = root_node
+            .breadth_first()
+            .map(|node| node.metadata()[0])
+            .collect();
+        assert_eq!(vec![1, 10, 2, 99], breadth_first);
+    }
+
+    #[test]
+    fn example_round_trip() {
+        let input = vec![2, 3, 0, 3, 10, 11, 12, 1, 1, 0, 1, 99, 2, 1, 1, 2];
+        let root_node = Node::read(&mut input.iter()).unwrap();
+        assert_eq!(input, root_node.to_numbers());
+
+        let mut text = Vec::new();
+        root_node.write_numbers(&mut text).unwrap();
+        assert_eq!(
+            "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2",
+            String::from_utf8(text).unwrap()
+        );
+    }
+
+    #[test]
+    fn example_pretty_print() {
+        let expected = "\
+Node (value 66) metadata: 1 1 2
+  Node (value 33) metadata: 10 11 12
+  Node (value 0) metadata: 2
+    Node (value 99) metadata: 99
+";
+        assert_eq!(expected, example_tree().to_string());
+    }
+
+    /// A chain of nodes, each of which refers to its only child twice.
+    fn doubling_chain(depth: usize) -> Node {
+        let mut input = Vec::with_capacity(3 * depth + 3);
+        for _ in 0..depth {
+            input.push(1);
+            input.push(2);
+        }
+        input.extend(&[0, 1, 1]);
+        input.extend(std::iter::repeat_n(1, 2 * depth));
+
+        Node::read(&mut input.iter()).unwrap()
+    }
+
+    #[test]
+    fn repeated_references_are_only_evaluated_once() {
+        assert_eq!(Some(1 << 62), doubling_chain(62).value());
+    }
+
+    #[test]
+    fn value_overflow_is_detected() {
+        assert_eq!(None, doubling_chain(63).value());
+    }
+
+    #[test]
+    fn metadata_out_of_range_is_ignored() {
+        let input = vec![1, 4, 0, 1, 7, 0, -1, 2, i32::MIN];
+        let root_node = Node::read(&mut input.iter()).unwrap();
+        assert_eq!(Some(0), root_node.value());
     }
 }
@@ -1,19 +1,19 @@
-use std::num::ParseIntError;
-use day8::{Node, errors::TreeParseError};
+use day8::{errors::TreeParseError, stream, Node};
 
 fn main() -> Result<(), TreeParseError> {
-    let input = include_str!("puzzle_input.txt")
-        .split_whitespace()
-        .map(|n| n.parse())
-        .collect::<Result<Vec<i32>, ParseIntError>>()?;
-    
-    let mut iterator = input.iter();
-    let root_node = Node::read(&mut iterator)?;
+    let input = include_str!("puzzle_input.txt");
 
-    println!("The sum of all metadata is {}", root_node.sum_metadata());
+    println!(
+        "The sum of all metadata is {}",
+        stream::sum_metadata(input.as_bytes())?
+    );
 
-    println!("The value of the root node is {}.", root_node.value());
+    let root_node = Node::read_from(input.as_bytes())?;
+
+    match root_node.value() {
+        Some(value) => println!("The value of the root node is {}.", value),
+        None => println!("The value of the root node is too large to calculate."),
+    }
 
     Ok(())
 }
-
@@ -0,0 +1,143 @@
+use crate::errors::TreeParseError;
+use crate::Reader;
+use std::io::{BufReader, Bytes, Read};
+
+/// Parses whitespace-separated numbers from a reader one at a time.
+pub struct Numbers<R> {
+    bytes: Bytes<BufReader<R>>,
+}
+
+impl<R: Read> Numbers<R> {
+    pub fn new(input: R) -> Numbers<R> {
+        Numbers {
+            bytes: BufReader::new(input).bytes(),
+        }
+    }
+}
+
+impl<R: Read> Iterator for Numbers<R> {
+    type Item = Result<i32, TreeParseError>;
+
+    fn next(&mut self) -> Option<Result<i32, TreeParseError>> {
+        let mut token = Vec::new();
+
+        for byte in &mut self.bytes {
+            match byte {
+                Ok(byte) if byte.is_ascii_whitespace() => {
+                    if !token.is_empty() {
+                        break;
+                    }
+                }
+                Ok(byte) => token.push(byte),
+                Err(error) => return Some(Err(error.into())),
+            }
+        }
+
+        if token.is_empty() {
+            return None;
+        }
+
+        Some(parse_number(&token))
+    }
+}
+
+fn parse_number(token: &[u8]) -> Result<i32, TreeParseError> {
+    let text = std::str::from_utf8(token).map_err(|_| TreeParseError::InvalidNumber)?;
+
+    Ok(text.parse()?)
+}
+
+/// Finds the sum of all of the metadata in a tree, reading the numbers from the
+/// input as it goes. The tree itself is never built: only the number of children
+/// and metadata entries still to come for each unfinished node are kept.
+///
+/// Returns `TreeParseError::Overflow` if the sum is too large to fit in an `i64`.
+pub fn sum_metadata<R: Read>(input: R) -> Result<i64, TreeParseError> {
+    let mut reader = Reader::new(Numbers::new(input));
+    let mut stack = vec![reader.read_counts()?];
+    let mut total: i64 = 0;
+
+    while let Some((children_remaining, metadata_count)) = stack.last_mut() {
+        if *children_remaining > 0 {
+            *children_remaining -= 1;
+            let child = reader.read_counts()?;
+            stack.push(child);
+            continue;
+        }
+
+        for _ in 0..*metadata_count {
+            let offset = reader.offset;
+            total = total
+                .checked_add(i64::from(reader.read_number()?))
+                .ok_or(TreeParseError::Overflow { offset })?;
+        }
+
+        stack.pop();
+    }
+
+    reader.finish()?;
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Node;
+
+    const EXAMPLE: &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+
+    #[test]
+    fn example_sum_of_metadata() {
+        assert_eq!(Ok(138), sum_metadata(EXAMPLE.as_bytes()));
+    }
+
+    #[test]
+    fn sums_beyond_i32() {
+        let input = "1 2 0 2 2147483647 2147483647 2147483647 2147483647";
+        let expected = 4 * i64::from(i32::MAX);
+
+        assert_eq!(Ok(expected), sum_metadata(input.as_bytes()));
+        assert_eq!(
+            Some(expected),
+            Node::read_from(input.as_bytes()).unwrap().sum_metadata()
+        );
+    }
+
+    #[test]
+    fn numbers_split_on_any_whitespace() {
+        let numbers: Result<Vec<i32>, TreeParseError> =
+            Numbers::new("  1\t-2\n\n30 \r\n4".as_bytes()).collect();
+        assert_eq!(Ok(vec![1, -2, 30, 4]), numbers);
+    }
+
+    #[test]
+    fn read_from_matches_read() {
+        let streamed = Node::read_from(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            vec![2, 3, 0, 3, 10, 11, 12, 1, 1, 0, 1, 99, 2, 1, 1, 2],
+            streamed.to_numbers()
+        );
+        assert_eq!(Some(66), streamed.value());
+    }
+
+    #[test]
+    fn streaming_errors() {
+        assert_eq!(
+            Err(TreeParseError::InvalidNumber),
+            sum_metadata("0 1 x".as_bytes())
+        );
+        assert_eq!(
+            Err(TreeParseError::TrailingData { offset: 3 }),
+            sum_metadata("0 1 5 7".as_bytes())
+        );
+        assert_eq!(
+            Err(TreeParseError::MissingData { offset: 6 }),
+            sum_metadata("1 2 0 1 99 1".as_bytes())
+        );
+        assert_eq!(
+            Err(TreeParseError::NegativeCount { offset: 3 }),
+            Node::read_from("1 1 0 -1 5".as_bytes()).map(|_| ())
+        );
+    }
+}
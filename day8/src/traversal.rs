@@ -0,0 +1,49 @@
+use crate::Node;
+use std::collections::VecDeque;
+
+/// Visits each node before its children, and the children in order.
+pub struct DepthFirst<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> DepthFirst<'a> {
+    pub(crate) fn new(root: &'a Node) -> DepthFirst<'a> {
+        DepthFirst { stack: vec![root] }
+    }
+}
+
+impl<'a> Iterator for DepthFirst<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children().iter().rev());
+
+        Some(node)
+    }
+}
+
+/// Visits the nodes one level at a time, starting from the root.
+pub struct BreadthFirst<'a> {
+    queue: VecDeque<&'a Node>,
+}
+
+impl<'a> BreadthFirst<'a> {
+    pub(crate) fn new(root: &'a Node) -> BreadthFirst<'a> {
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
+
+        BreadthFirst { queue }
+    }
+}
+
+impl<'a> Iterator for BreadthFirst<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        let node = self.queue.pop_front()?;
+        self.queue.extend(node.children());
+
+        Some(node)
+    }
+}
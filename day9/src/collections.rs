@@ -1,99 +1,311 @@
 use std::fmt;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct Node {
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: T,
     left: usize,
     right: usize,
 }
 
-pub struct CircularList {
-    nodes: Vec<Option<Node>>,
+/// Refers to an element of a `CircularList`. A position stays valid until the
+/// element it refers to is removed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Position(usize);
+
+/// A circular doubly-linked list. The elements are kept in one vector and linked
+/// together by their indices, and the slots of removed elements are reused.
+#[derive(Clone)]
+pub struct CircularList<T> {
+    nodes: Vec<Option<Node<T>>>,
+    free: Vec<usize>,
+    head: Option<usize>,
+    len: usize,
 }
 
-impl CircularList {
-    pub fn with_capacity(capacity: usize) -> CircularList {
-        let mut nodes = vec![None; capacity];
-        nodes[0] = Some(Node { left: 0, right: 0 });
+impl<T> CircularList<T> {
+    pub fn new() -> CircularList<T> {
+        CircularList::with_capacity(0)
+    }
 
-        CircularList { nodes }
+    /// Creates an empty list with room for `capacity` elements. The list grows
+    /// as needed if more elements are added.
+    pub fn with_capacity(capacity: usize) -> CircularList<T> {
+        CircularList {
+            nodes: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            head: None,
+            len: 0,
+        }
     }
 
-    pub fn left_of(&self, existing_value: usize) -> Option<usize> {
-        let node = self.nodes.get(existing_value)?;
-        node.map(|n| n.left)
+    pub fn len(&self) -> usize {
+        self.len
     }
 
-    pub fn right_of(&self, existing_value: usize) -> Option<usize> {
-        let node = self.nodes.get(existing_value)?;
-        node.map(|n| n.right)
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
 
-    pub fn insert_after(&mut self, existing_value: usize, new_value: usize) {
-        let right_value = self
-            .right_of(existing_value)
-            .expect("Value to insert before does not exist");
+    /// The element that iteration starts from, which is the first element added
+    /// unless it has since been removed.
+    pub fn head(&self) -> Option<Position> {
+        self.head.map(Position)
+    }
 
-        let new_node = Node {
-            left: existing_value,
-            right: right_value,
-        };
-        self.nodes[new_value] = Some(new_node);
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.node(position.0).map(|node| &node.value)
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        match self.nodes.get_mut(position.0) {
+            Some(Some(node)) => Some(&mut node.value),
+            _ => None,
+        }
+    }
+
+    /// Adds an element just to the left of the head, so that it comes last when iterating.
+    pub fn push_back(&mut self, value: T) -> Position {
+        match self.head {
+            Some(head) => {
+                let last = self.nodes[head].as_ref().unwrap().left;
+                Position(self.link_after(last, value))
+            }
+            None => {
+                let index = self.allocate(Node {
+                    value,
+                    left: 0,
+                    right: 0,
+                });
+                let node = self.nodes[index].as_mut().unwrap();
+                node.left = index;
+                node.right = index;
+                self.head = Some(index);
+                Position(index)
+            }
+        }
+    }
+
+    /// A cursor at the head of the list.
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.head,
+            list: self,
+        }
+    }
+
+    /// A cursor at the given position, or `None` if there is no element there.
+    pub fn cursor_mut_at(&mut self, position: Position) -> Option<CursorMut<'_, T>> {
+        self.node(position.0)?;
+
+        Some(CursorMut {
+            current: Some(position.0),
+            list: self,
+        })
+    }
+
+    /// Iterates once around the list, starting from the head and moving right.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            list: self,
+            next: self.head,
+            remaining: self.len,
+        }
+    }
+
+    /// Iterates once around the list, starting from the given position and moving right.
+    pub fn iter_from(&self, position: Position) -> Iter<'_, T> {
+        let start = self.node(position.0).map(|_| position.0);
+
+        Iter {
+            list: self,
+            next: start,
+            remaining: if start.is_some() { self.len } else { 0 },
+        }
+    }
+
+    fn node(&self, index: usize) -> Option<&Node<T>> {
+        self.nodes.get(index)?.as_ref()
+    }
 
-        let previous_node = self
-            .nodes
-            .get_mut(existing_value)
-            .expect("Node to insert after does not exist")
-            .as_mut()
-            .expect("Node to insert after is not initialised");
+    fn allocate(&mut self, node: Node<T>) -> usize {
+        self.len += 1;
 
-        previous_node.right = new_value;
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                index
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Inserts a value to the right of an existing element and returns its index.
+    fn link_after(&mut self, existing: usize, value: T) -> usize {
+        let right = self.nodes[existing].as_ref().unwrap().right;
+        let index = self.allocate(Node {
+            value,
+            left: existing,
+            right,
+        });
 
-        let next_node = self
-            .nodes
-            .get_mut(right_value)
-            .expect("Node to insert before does not exist")
-            .as_mut()
-            .expect("Node to insert before is not initialised");
+        self.nodes[existing].as_mut().unwrap().right = index;
+        self.nodes[right].as_mut().unwrap().left = index;
 
-        next_node.left = new_value;
+        index
     }
 
-    pub fn remove(&mut self, value_to_remove: usize) {
-        if let Some(removed_node) = self.nodes[value_to_remove] {
-            self.nodes[value_to_remove] = None;
+    /// Removes an existing element, returning its value and the index of the
+    /// element to its right (if there is one left).
+    fn unlink(&mut self, index: usize) -> (T, Option<usize>) {
+        let node = self.nodes[index].take().unwrap();
+        self.free.push(index);
+        self.len -= 1;
+
+        if self.len == 0 {
+            self.head = None;
+            return (node.value, None);
+        }
 
-            let mut left_node = self
-                .nodes
-                .get_mut(removed_node.left)
-                .expect("Index of left node out of range")
-                .as_mut()
-                .expect("Left node does not exist");
-            left_node.right = removed_node.right;
+        self.nodes[node.left].as_mut().unwrap().right = node.right;
+        self.nodes[node.right].as_mut().unwrap().left = node.left;
 
-            let mut right_node = self
-                .nodes
-                .get_mut(removed_node.right)
-                .expect("Index of right node out of range")
-                .as_mut()
-                .expect("Right node does not exist");
-            right_node.left = removed_node.left;
+        if self.head == Some(index) {
+            self.head = Some(node.right);
         }
+
+        (node.value, Some(node.right))
     }
 }
 
-impl fmt::Debug for CircularList {
+impl<T> Default for CircularList<T> {
+    fn default() -> CircularList<T> {
+        CircularList::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CircularList<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut number = 0;
-        write!(f, "CircularList {{ ")?;
-        while let Some(Some(node)) = self.nodes.get(number) {
-            write!(f, "{} ", number)?;
-            number = node.right;
-            if number == 0 {
-                break;
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Extend<T> for CircularList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        for value in values {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for CircularList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> CircularList<T> {
+        let mut list = CircularList::new();
+        list.extend(values);
+        list
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CircularList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+pub struct Iter<'a, T> {
+    list: &'a CircularList<T>,
+    next: Option<usize>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = self.list.node(self.next?)?;
+        self.next = Some(node.right);
+        self.remaining -= 1;
+
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// Points at one element of a `CircularList` and can move around it, insert
+/// new elements and remove the current one. A cursor on an empty list points
+/// at nothing.
+pub struct CursorMut<'a, T> {
+    list: &'a mut CircularList<T>,
+    current: Option<usize>,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    pub fn position(&self) -> Option<Position> {
+        self.current.map(Position)
+    }
+
+    pub fn current(&self) -> Option<&T> {
+        self.list.node(self.current?).map(|node| &node.value)
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        match self.list.nodes.get_mut(self.current?) {
+            Some(Some(node)) => Some(&mut node.value),
+            _ => None,
+        }
+    }
+
+    /// Moves `steps` elements to the left (anticlockwise).
+    pub fn move_left(&mut self, steps: usize) {
+        self.step(steps, |node| node.left);
+    }
+
+    /// Moves `steps` elements to the right (clockwise).
+    pub fn move_right(&mut self, steps: usize) {
+        self.step(steps, |node| node.right);
+    }
+
+    fn step(&mut self, steps: usize, next: impl Fn(&Node<T>) -> usize) {
+        if let Some(mut index) = self.current {
+            // Going all the way round the circle ends up back in the same place.
+            for _ in 0..steps % self.list.len {
+                index = next(self.list.nodes[index].as_ref().unwrap());
             }
+            self.current = Some(index);
         }
+    }
+
+    /// Inserts a value to the right of the current element and moves the cursor
+    /// onto it. In an empty list, the value becomes the only element.
+    pub fn insert_after(&mut self, value: T) -> Position {
+        let index = match self.current {
+            Some(current) => self.list.link_after(current, value),
+            None => self.list.push_back(value).0,
+        };
+        self.current = Some(index);
+
+        Position(index)
+    }
+
+    /// Removes the current element and moves the cursor onto the element to its
+    /// right. Returns `None` if the list is empty.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let (value, next) = self.list.unlink(self.current?);
+        self.current = next;
 
-        write!(f, "}}")
+        Some(value)
     }
 }
 
@@ -101,35 +313,104 @@ impl fmt::Debug for CircularList {
 mod tests {
     use super::*;
 
+    fn contents(list: &CircularList<usize>) -> Vec<usize> {
+        list.iter().cloned().collect()
+    }
+
     #[test]
     fn test_circular_list_insert() {
         let mut circle = CircularList::with_capacity(3);
+        circle.push_back(0);
 
-        circle.insert_after(0, 1);
+        let mut cursor = circle.cursor_mut();
+        cursor.insert_after(1);
+        cursor.move_left(1);
+        assert_eq!(Some(&0), cursor.current());
+        cursor.move_right(1);
+        assert_eq!(Some(&1), cursor.current());
 
-        assert_eq!(Some(0), circle.left_of(1));
-        assert_eq!(Some(0), circle.right_of(1));
+        cursor.insert_after(2);
+        cursor.move_right(1);
+        assert_eq!(Some(&0), cursor.current());
+        cursor.move_left(2);
+        assert_eq!(Some(&1), cursor.current());
 
-        assert_eq!(Some(1), circle.left_of(0));
-        assert_eq!(Some(1), circle.right_of(0));
+        assert_eq!(vec![0, 1, 2], contents(&circle));
+    }
 
-        circle.insert_after(1, 2);
+    #[test]
+    fn test_circular_list_remove() {
+        let mut circle: CircularList<usize> = (0..3).collect();
+
+        let mut cursor = circle.cursor_mut();
+        cursor.move_right(1);
+        assert_eq!(Some(1), cursor.remove_current());
+        assert_eq!(Some(&2), cursor.current());
+        cursor.move_right(1);
+        assert_eq!(Some(&0), cursor.current());
 
-        assert_eq!(Some(2), circle.right_of(1));
-        assert_eq!(Some(0), circle.right_of(2));
-        assert_eq!(Some(1), circle.left_of(2));
+        assert_eq!(vec![0, 2], contents(&circle));
     }
 
     #[test]
-    fn test_circular_list_remove() {
-        let mut circle = CircularList::with_capacity(3);
-        circle.insert_after(0, 1);
-        circle.insert_after(1, 2);
-        circle.remove(1);
-
-        assert_eq!(Some(2), circle.right_of(0));
-        assert_eq!(Some(2), circle.left_of(0));
-        assert_eq!(Some(0), circle.left_of(2));
-        assert_eq!(Some(0), circle.right_of(2));
+    fn removing_everything_empties_the_list() {
+        let mut circle: CircularList<usize> = (0..3).collect();
+
+        let mut cursor = circle.cursor_mut();
+        for expected in 0..3 {
+            assert_eq!(Some(expected), cursor.remove_current());
+        }
+        assert_eq!(None, cursor.remove_current());
+        assert_eq!(None, cursor.current());
+
+        cursor.insert_after(7);
+        assert_eq!(vec![7], contents(&circle));
+    }
+
+    #[test]
+    fn moves_wrap_around_the_circle() {
+        let mut circle: CircularList<char> = "abcde".chars().collect();
+
+        let mut cursor = circle.cursor_mut();
+        cursor.move_left(1);
+        assert_eq!(Some(&'e'), cursor.current());
+        cursor.move_right(1_000_003);
+        assert_eq!(Some(&'c'), cursor.current());
+    }
+
+    #[test]
+    fn grows_beyond_initial_capacity_and_reuses_slots() {
+        let mut circle = CircularList::with_capacity(2);
+        let first = circle.push_back(0);
+
+        let mut cursor = circle.cursor_mut_at(first).unwrap();
+        for n in 1..100 {
+            cursor.insert_after(n);
+        }
+        cursor.move_right(1);
+        for _ in 0..50 {
+            cursor.remove_current();
+        }
+        cursor.move_left(1);
+        for n in 100..150 {
+            cursor.insert_after(n);
+        }
+
+        assert_eq!(100, circle.len());
+        assert_eq!(100, circle.nodes.len());
+        let expected: Vec<usize> = (50..150).collect();
+        assert_eq!(expected, contents(&circle));
+    }
+
+    #[test]
+    fn iteration_from_a_position() {
+        let mut circle = CircularList::new();
+        circle.push_back("zero");
+        let one = circle.push_back("one");
+        circle.push_back("two");
+
+        let words: Vec<&str> = circle.iter_from(one).cloned().collect();
+        assert_eq!(vec!["one", "two", "zero"], words);
+        assert_eq!("[\"zero\", \"one\", \"two\"]", format!("{:?}", circle));
     }
 }
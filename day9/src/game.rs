@@ -0,0 +1,337 @@
+use crate::collections::{CircularList, Position};
+use crate::results::{GameResult, ScoringEvent};
+use crate::rules::MarbleRules;
+use std::{fmt, io};
+
+#[derive(Debug)]
+pub struct MarbleGame {
+    marbles: CircularList<usize>,
+    current_marble: Option<Position>,
+    rules: MarbleRules,
+}
+
+impl MarbleGame {
+    /// Starts a game with the puzzle's rules.
+    pub fn with_capacity(marble_count: usize) -> MarbleGame {
+        MarbleGame::with_rules(marble_count, MarbleRules::puzzle())
+    }
+
+    pub fn with_rules(marble_count: usize, rules: MarbleRules) -> MarbleGame {
+        let mut marbles = CircularList::with_capacity(marble_count + 1);
+        let current_marble = Some(marbles.push_back(0));
+
+        MarbleGame {
+            marbles,
+            current_marble,
+            rules,
+        }
+    }
+
+    pub fn rules(&self) -> &MarbleRules {
+        &self.rules
+    }
+
+    /// The number of the current marble, or `None` if the circle is empty.
+    pub fn current_marble(&self) -> Option<usize> {
+        self.marbles.get(self.current_marble?).cloned()
+    }
+
+    /// The marbles in clockwise order, starting from marble 0 (or, once marble 0
+    /// has been removed, the marble which took its place).
+    pub fn marbles(&self) -> impl Iterator<Item = &usize> {
+        self.marbles.iter()
+    }
+
+    /// Saves the state of the circle so that it can be restored later.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            marbles: self.marbles.clone(),
+            current_marble: self.current_marble,
+        }
+    }
+
+    /// Puts the circle back to the state it was in when the snapshot was taken.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.marbles = snapshot.marbles.clone();
+        self.current_marble = snapshot.current_marble;
+    }
+
+    /// Places a marble in the circle and returns the resulting score.
+    pub fn place_marble(&mut self, number: usize) -> usize {
+        let scoring = self.rules.scoring;
+        self.play_marble(number)
+            .map_or(0, |removed| scoring(number, removed))
+    }
+
+    /// Plays a marble, either placing it in the circle or, if it scores,
+    /// removing a marble from the circle. Returns the removed marble.
+    ///
+    /// If a scoring marble is played when the circle is empty, there is nothing
+    /// to remove and the marble scores nothing.
+    pub fn play_marble(&mut self, number: usize) -> Option<usize> {
+        let rules = self.rules;
+        let mut cursor = match self.current_marble {
+            Some(position) => self
+                .marbles
+                .cursor_mut_at(position)
+                .expect("The current marble is always in the circle"),
+            None => self.marbles.cursor_mut(),
+        };
+
+        let removed = if rules.scoring_multiple > 0 && number.is_multiple_of(rules.scoring_multiple)
+        {
+            cursor.move_left(rules.removal_offset);
+            cursor.remove_current()
+        } else {
+            cursor.move_right(rules.insert_offset);
+            cursor.insert_after(number);
+            None
+        };
+
+        self.current_marble = cursor.position();
+
+        removed
+    }
+}
+
+/// Draws the circle in the style of the puzzle, with each marble right-aligned
+/// in a column three characters wide and the current marble in parentheses.
+/// The parentheses take one column from the marble after the current one.
+impl fmt::Display for MarbleGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let current = self.current_marble();
+        let mut after_current = false;
+
+        for &marble in self.marbles() {
+            if Some(marble) == current {
+                write!(f, "{:>4}", format!("({})", marble))?;
+                after_current = true;
+            } else if after_current {
+                write!(f, "{:>2}", marble)?;
+                after_current = false;
+            } else {
+                write!(f, "{:>3}", marble)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The state of a `MarbleGame`'s circle at one point in the game.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    marbles: CircularList<usize>,
+    current_marble: Option<Position>,
+}
+
+/// Plays marbles 1 to `last_marble` in turn, with the players taking turns.
+///
+/// # Panics
+///
+/// Panics if there are no players.
+pub fn play(players: usize, last_marble: usize, rules: MarbleRules) -> GameResult {
+    assert!(players > 0, "A game needs at least one player");
+
+    let mut result = GameResult::new(players);
+    let mut game = MarbleGame::with_rules(last_marble, rules);
+    let mut player: usize = 0;
+
+    for n in 1..=last_marble {
+        if let Some(removed) = game.play_marble(n) {
+            result.record(ScoringEvent {
+                player,
+                marble: n,
+                removed,
+                points: (rules.scoring)(n, removed),
+            });
+        }
+        player = (player + 1) % players;
+    }
+
+    result
+}
+
+/// Plays a game and writes the circle after every turn, one line per turn, in
+/// the same way as the puzzle's example. Each line starts with the player who
+/// has just taken a turn, counting from 1. The lines grow with the number of
+/// marbles, so this is only suitable for small games.
+///
+/// # Panics
+///
+/// Panics if there are no players.
+pub fn trace<W: io::Write>(
+    players: usize,
+    last_marble: usize,
+    rules: MarbleRules,
+    out: &mut W,
+) -> io::Result<()> {
+    assert!(players > 0, "A game needs at least one player");
+
+    let mut game = MarbleGame::with_rules(last_marble, rules);
+    writeln!(out, "[-]{}", game)?;
+
+    for n in 1..=last_marble {
+        game.place_marble(n);
+        writeln!(out, "[{}]{}", (n - 1) % players + 1, game)?;
+    }
+
+    Ok(())
+}
+
+pub fn high_score(players: usize, last_marble: usize) -> usize {
+    play(players, last_marble, MarbleRules::puzzle()).high_score()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{self, ScoreEntry};
+
+    #[test]
+    fn nine_player_high_score() {
+        assert_eq!(32, high_score(9, 25));
+    }
+
+    #[test]
+    fn ten_player_high_score() {
+        assert_eq!(8317, high_score(10, 1618));
+    }
+
+    #[test]
+    fn thirteen_player_high_score() {
+        assert_eq!(146_373, high_score(13, 7999));
+    }
+
+    #[test]
+    fn seventeen_player_high_score() {
+        assert_eq!(2764, high_score(17, 1104));
+    }
+
+    #[test]
+    fn twenty_one_player_high_score() {
+        assert_eq!(54_718, high_score(21, 6111));
+    }
+
+    #[test]
+    fn thirty_player_high_score() {
+        assert_eq!(37_305, high_score(30, 5807));
+    }
+
+    #[test]
+    fn score_history() {
+        let result = play(9, 25, MarbleRules::puzzle());
+
+        assert_eq!(vec![0, 0, 0, 0, 32, 0, 0, 0, 0], result.scores());
+        assert_eq!(
+            &[ScoreEntry {
+                marble: 23,
+                points: 32,
+                total: 32
+            }],
+            result.scoreboard().history(4)
+        );
+        assert!(result.scoreboard().history(0).is_empty());
+        assert_eq!(4, result.winner());
+        assert_eq!(
+            &[ScoringEvent {
+                player: 4,
+                marble: 23,
+                removed: 9,
+                points: 32
+            }],
+            result.events()
+        );
+    }
+
+    #[test]
+    fn variant_rules() {
+        let removed_only = MarbleRules {
+            scoring: rules::removed_only,
+            ..MarbleRules::puzzle()
+        };
+        assert_eq!(9, play(9, 25, removed_only).high_score());
+
+        // Placing each marble straight after the current one and removing the
+        // current marble means every fifth marble takes back the one before it.
+        let stack = MarbleRules {
+            scoring_multiple: 5,
+            removal_offset: 0,
+            insert_offset: 0,
+            scoring: rules::marble_plus_removed,
+        };
+        let scoreboard = play(2, 10, stack);
+        assert_eq!(vec![5 + 4, 10 + 9], scoreboard.scores());
+    }
+
+    fn trace_to_string(players: usize, last_marble: usize) -> String {
+        let mut out = Vec::new();
+        trace(players, last_marble, MarbleRules::puzzle(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn example_trace() {
+        let expected = "\
+[-] (0)
+[1]  0 (1)
+[2]  0 (2) 1
+[3]  0  2  1 (3)
+[4]  0 (4) 2  1  3
+[5]  0  4  2 (5) 1  3
+[6]  0  4  2  5  1 (6) 3
+[7]  0  4  2  5  1  6  3 (7)
+[8]  0 (8) 4  2  5  1  6  3  7
+[9]  0  8  4 (9) 2  5  1  6  3  7
+[1]  0  8  4  9  2(10) 5  1  6  3  7
+";
+        assert_eq!(expected, trace_to_string(9, 10));
+
+        let last_lines: Vec<String> = trace_to_string(9, 25)
+            .lines()
+            .skip(22)
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            vec![
+                "[4]  0 16  8 17  4 18  9 19  2 20 10 21  5(22)11  1 12  6 13  3 14  7 15",
+                "[5]  0 16  8 17  4 18(19) 2 20 10 21  5 22 11  1 12  6 13  3 14  7 15",
+                "[6]  0 16  8 17  4 18 19  2(24)20 10 21  5 22 11  1 12  6 13  3 14  7 15",
+                "[7]  0 16  8 17  4 18 19  2 24 20(25)10 21  5 22 11  1 12  6 13  3 14  7 15",
+            ],
+            last_lines
+        );
+    }
+
+    #[test]
+    fn snapshot_and_restore() {
+        let mut game = MarbleGame::with_capacity(25);
+        for n in 1..=20 {
+            game.place_marble(n);
+        }
+
+        let snapshot = game.snapshot();
+        let before = game.to_string();
+        let scores: Vec<usize> = (21..=25).map(|n| game.place_marble(n)).collect();
+
+        game.restore(&snapshot);
+        assert_eq!(before, game.to_string());
+        assert_eq!(Some(20), game.current_marble());
+        assert_eq!(
+            scores,
+            (21..=25).map(|n| game.place_marble(n)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn scoring_with_an_empty_circle() {
+        let every_marble = MarbleRules {
+            scoring_multiple: 1,
+            removal_offset: 0,
+            ..MarbleRules::puzzle()
+        };
+
+        // Marble 1 takes marble 0, leaving nothing for marble 2 to take.
+        assert_eq!(vec![1, 0], play(2, 2, every_marble).scores());
+    }
+}
@@ -0,0 +1,9 @@
+pub mod collections;
+mod game;
+mod parsing;
+mod results;
+pub mod rules;
+pub use crate::game::{high_score, play, trace, MarbleGame, Snapshot};
+pub use crate::parsing::{parse_games, GameDescription, ParseError};
+pub use crate::results::{GameResult, ScoringEvent};
+pub use crate::rules::{MarbleRules, ScoreEntry, Scoreboard};
@@ -0,0 +1,142 @@
+use crate::game::{play, trace};
+use crate::results::GameResult;
+use crate::rules::MarbleRules;
+use std::{error, fmt, io};
+
+/// The number of players and the last marble played in a game.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct GameDescription {
+    pub players: usize,
+    pub last_marble: usize,
+}
+
+impl GameDescription {
+    /// Plays the game with the puzzle's rules.
+    pub fn play(&self) -> GameResult {
+        play(self.players, self.last_marble, MarbleRules::puzzle())
+    }
+
+    /// Writes the circle after every turn of the game, with the puzzle's rules.
+    pub fn trace<W: io::Write>(&self, out: &mut W) -> io::Result<()> {
+        trace(self.players, self.last_marble, MarbleRules::puzzle(), out)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseError {
+    IncorrectFormat { line: usize },
+    NoPlayers { line: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::IncorrectFormat { line } => write!(
+                f,
+                "Line {}: expected \"N players; last marble is worth M points\"",
+                line
+            ),
+            ParseError::NoPlayers { line } => {
+                write!(f, "Line {}: a game needs at least one player", line)
+            }
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Reads game descriptions of the form "N players; last marble is worth M points",
+/// one per line. Anything after a colon, such as ": high score is 8317" in the
+/// puzzle's examples, is ignored. Blank lines are skipped, and line numbers in
+/// errors count from 1.
+pub fn parse_games(input: &str) -> Result<Vec<GameDescription>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| parse_game(line, index + 1))
+        .collect()
+}
+
+fn parse_game(line: &str, line_number: usize) -> Result<GameDescription, ParseError> {
+    let description = line.split(':').next().unwrap_or("").trim();
+    let description = description.strip_suffix('.').unwrap_or(description);
+    let words: Vec<&str> = description.split_whitespace().collect();
+
+    let (players, last_marble) = match words.as_slice() {
+        [players, players_word, last, marble, is, worth, points, points_word]
+            if players_word.eq_ignore_ascii_case("players;")
+                && last.eq_ignore_ascii_case("last")
+                && marble.eq_ignore_ascii_case("marble")
+                && is.eq_ignore_ascii_case("is")
+                && worth.eq_ignore_ascii_case("worth")
+                && points_word.eq_ignore_ascii_case("points") =>
+        {
+            (players.parse().ok(), points.parse().ok())
+        }
+        _ => (None, None),
+    };
+
+    match (players, last_marble) {
+        (Some(0), Some(_)) => Err(ParseError::NoPlayers { line: line_number }),
+        (Some(players), Some(last_marble)) => Ok(GameDescription {
+            players,
+            last_marble,
+        }),
+        _ => Err(ParseError::IncorrectFormat { line: line_number }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_puzzle_input() {
+        assert_eq!(
+            Ok(vec![GameDescription {
+                players: 476,
+                last_marble: 71_431
+            }]),
+            parse_games("476 players; last marble is worth 71431 points\n")
+        );
+    }
+
+    #[test]
+    fn parse_examples_with_answers() {
+        let input = "\
+10 players; last marble is worth 1618 points: high score is 8317
+
+13 Players; Last Marble is worth 7999 points: high score is 146373
+";
+        assert_eq!(
+            Ok(vec![
+                GameDescription {
+                    players: 10,
+                    last_marble: 1618
+                },
+                GameDescription {
+                    players: 13,
+                    last_marble: 7999
+                }
+            ]),
+            parse_games(input)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err(ParseError::IncorrectFormat { line: 2 }),
+            parse_games("9 players; last marble is worth 25 points\n9 players; last marble 25")
+        );
+        assert_eq!(
+            Err(ParseError::IncorrectFormat { line: 1 }),
+            parse_games("nine players; last marble is worth 25 points")
+        );
+        assert_eq!(
+            Err(ParseError::NoPlayers { line: 1 }),
+            parse_games("0 players; last marble is worth 25 points")
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::rules::Scoreboard;
+
+/// A turn on which a player removed a marble from the circle.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ScoringEvent {
+    pub player: usize,
+    /// The number of the marble played on the turn.
+    pub marble: usize,
+    /// The number of the marble taken from the circle.
+    pub removed: usize,
+    pub points: usize,
+}
+
+/// The outcome of a game: every turn on which a player scored, from which each
+/// player's score is worked out.
+#[derive(Debug, Clone)]
+pub struct GameResult {
+    players: usize,
+    events: Vec<ScoringEvent>,
+}
+
+impl GameResult {
+    pub(crate) fn new(players: usize) -> GameResult {
+        GameResult {
+            players,
+            events: Vec::new(),
+        }
+    }
+
+    pub(crate) fn record(&mut self, event: ScoringEvent) {
+        self.events.push(event);
+    }
+
+    /// Replays the scoring events onto a scoreboard, which holds each player's
+    /// history of scoring turns and running totals.
+    pub fn scoreboard(&self) -> Scoreboard {
+        let mut scoreboard = Scoreboard::new(self.players);
+        for event in &self.events {
+            scoreboard.record(event.player, event.marble, event.points);
+        }
+
+        scoreboard
+    }
+
+    pub fn scores(&self) -> Vec<usize> {
+        let mut scores = vec![0; self.players];
+        for event in &self.events {
+            scores[event.player] += event.points;
+        }
+
+        scores
+    }
+
+    /// Every turn on which a marble was removed, in the order they were played.
+    pub fn events(&self) -> &[ScoringEvent] {
+        &self.events
+    }
+
+    /// The index of the player with the highest score. If several players share
+    /// the highest score, the first of them wins.
+    pub fn winner(&self) -> usize {
+        let scores = self.scores();
+        let high_score = scores.iter().cloned().max().unwrap_or(0);
+
+        scores
+            .iter()
+            .position(|&score| score == high_score)
+            .unwrap_or(0)
+    }
+
+    pub fn high_score(&self) -> usize {
+        self.scores().into_iter().max().unwrap_or(0)
+    }
+}
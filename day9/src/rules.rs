@@ -0,0 +1,106 @@
+/// The rules for placing marbles, so that variants of the game can be played.
+#[derive(Debug, Copy, Clone)]
+pub struct MarbleRules {
+    /// Marbles whose numbers are a multiple of this score points instead of
+    /// being placed. Zero means that no marble ever scores.
+    pub scoring_multiple: usize,
+    /// How many places anticlockwise from the current marble the marble
+    /// which is removed when scoring lies.
+    pub removal_offset: usize,
+    /// How many places clockwise from the current marble the marble which a new
+    /// marble is placed after lies.
+    pub insert_offset: usize,
+    /// Works out the points scored from the number of the marble being played
+    /// and the number of the marble removed from the circle.
+    pub scoring: fn(usize, usize) -> usize,
+}
+
+impl MarbleRules {
+    /// The rules given in the puzzle: every 23rd marble is kept along with the
+    /// marble 7 places anticlockwise, and other marbles are placed between the
+    /// marbles 1 and 2 places clockwise.
+    pub fn puzzle() -> MarbleRules {
+        MarbleRules {
+            scoring_multiple: 23,
+            removal_offset: 7,
+            insert_offset: 1,
+            scoring: marble_plus_removed,
+        }
+    }
+}
+
+impl Default for MarbleRules {
+    fn default() -> MarbleRules {
+        MarbleRules::puzzle()
+    }
+}
+
+/// The player keeps both the marble being played and the removed marble.
+pub fn marble_plus_removed(marble: usize, removed: usize) -> usize {
+    marble + removed
+}
+
+/// The player keeps only the removed marble.
+pub fn removed_only(_marble: usize, removed: usize) -> usize {
+    removed
+}
+
+/// Points scored by a player on one turn.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ScoreEntry {
+    /// The number of the marble played on the turn.
+    pub marble: usize,
+    pub points: usize,
+    /// The player's total score after the turn.
+    pub total: usize,
+}
+
+/// The scores of each player, along with the turns on which they scored.
+#[derive(Debug, Clone)]
+pub struct Scoreboard {
+    history: Vec<Vec<ScoreEntry>>,
+}
+
+impl Scoreboard {
+    pub fn new(players: usize) -> Scoreboard {
+        Scoreboard {
+            history: vec![Vec::new(); players],
+        }
+    }
+
+    pub fn players(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Adds the points scored by a player when playing a marble. Turns which
+    /// score nothing are not recorded.
+    pub fn record(&mut self, player: usize, marble: usize, points: usize) {
+        if points > 0 {
+            let total = self.score(player) + points;
+            self.history[player].push(ScoreEntry {
+                marble,
+                points,
+                total,
+            });
+        }
+    }
+
+    pub fn score(&self, player: usize) -> usize {
+        self.history[player].last().map_or(0, |entry| entry.total)
+    }
+
+    pub fn scores(&self) -> Vec<usize> {
+        (0..self.players())
+            .map(|player| self.score(player))
+            .collect()
+    }
+
+    /// The turns on which a player scored, in the order they were played.
+    pub fn history(&self, player: usize) -> &[ScoreEntry] {
+        &self.history[player]
+    }
+
+    pub fn high_score(&self) -> usize {
+        self.scores().into_iter().max().unwrap_or(0)
+    }
+}